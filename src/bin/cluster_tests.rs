@@ -1,5 +1,8 @@
 use image_clustering::generate_super_pixels;
 
 fn main() {
-    generate_super_pixels(String::from("D:\\Desktop\\cluster_test.jpg"))
+    if let Err(e) = generate_super_pixels("D:\\Desktop\\cluster_test.jpg") {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,129 @@
+use super::{ColorXyz, WhitePoint};
+use cgmath::{Matrix, Matrix3, SquareMatrix, Vector3};
+
+/// Cone response model used to carry colors from one reference white to another
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AdaptationMethod {
+    /// sharpened cone responses, what ICC profiles use
+    #[default]
+    Bradford,
+    /// Hunt-Pointer-Estevez cone responses
+    VonKries,
+}
+
+impl AdaptationMethod {
+    /// Matrix from XYZ to cone responses
+    fn cone_matrix(&self) -> Matrix3<f64> {
+        // written row by row, while cgmath expects columns
+        let rows = match self {
+            AdaptationMethod::Bradford => Matrix3::new(
+                0.8951, 0.2664, -0.1614, //
+                -0.7502, 1.7135, 0.0367, //
+                0.0389, -0.0685, 1.0296,
+            ),
+            AdaptationMethod::VonKries => Matrix3::new(
+                0.40024, 0.70760, -0.08081, //
+                -0.22630, 1.16532, 0.04570, //
+                0.0, 0.0, 0.91822,
+            ),
+        };
+
+        rows.transpose()
+    }
+
+    /// Converts a color seen under the `from` white to the color that looks the same under `to`
+    pub fn adapt(&self, xyz: &ColorXyz, from: WhitePoint, to: WhitePoint) -> ColorXyz {
+        if from == to {
+            return xyz.clone();
+        }
+
+        let cones = self.cone_matrix();
+        let inverse = cones
+            .invert()
+            .expect("cone response matrices are invertible");
+        let to_vector = |c: &ColorXyz| Vector3::new(c.x as f64, c.y as f64, c.z as f64);
+
+        let source = cones * to_vector(&from.xyz());
+        let destination = cones * to_vector(&to.xyz());
+        let scale = Matrix3::from_diagonal(Vector3::new(
+            destination.x / source.x,
+            destination.y / source.y,
+            destination.z / source.z,
+        ));
+
+        let adapted = inverse * scale * cones * to_vector(xyz);
+        ColorXyz {
+            x: adapted.x as f32,
+            y: adapted.y as f32,
+            z: adapted.z as f32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::{AdaptationMethod, ColorXyz, WhitePoint};
+
+    fn assert_close(expected: &ColorXyz, actual: &ColorXyz, tolerance: f32) {
+        let delta = [
+            expected.x - actual.x,
+            expected.y - actual.y,
+            expected.z - actual.z,
+        ];
+        assert!(
+            delta.iter().all(|d| d.abs() < tolerance),
+            "{:?} != {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn adapting_a_white_should_give_the_other_white() {
+        for method in [AdaptationMethod::Bradford, AdaptationMethod::VonKries] {
+            // Act
+            let actual = method.adapt(&WhitePoint::D65.xyz(), WhitePoint::D65, WhitePoint::D50);
+
+            // Assert
+            assert_close(&WhitePoint::D50.xyz(), &actual, 1e-3);
+        }
+    }
+
+    #[test]
+    fn bradford_should_match_reference_values() {
+        // Arrange
+        let red = ColorXyz {
+            x: 41.2456,
+            y: 21.2673,
+            z: 1.9334,
+        };
+
+        // Act
+        let actual = AdaptationMethod::Bradford.adapt(&red, WhitePoint::D65, WhitePoint::D50);
+
+        // Assert
+        let expected = ColorXyz {
+            x: 43.6075,
+            y: 22.2504,
+            z: 1.3932,
+        };
+        assert_close(&expected, &actual, 0.01);
+    }
+
+    #[test]
+    fn adapting_there_and_back_should_be_identity() {
+        // Arrange
+        let color = ColorXyz {
+            x: 20.0,
+            y: 30.0,
+            z: 40.0,
+        };
+
+        // Act
+        let there = AdaptationMethod::VonKries.adapt(&color, WhitePoint::D65, WhitePoint::A);
+        let back = AdaptationMethod::VonKries.adapt(&there, WhitePoint::A, WhitePoint::D65);
+
+        // Assert
+        assert_close(&color, &back, 1e-3);
+    }
+}
@@ -1,32 +1,28 @@
-use super::ColorXyz;
+use super::{ColorXyz, WhitePoint};
 use cgmath::Vector3;
 
-// d65 CIE 1931 reference values for rgb color space conversion
-const REFERENCE_X: f32 = 95.047;
-const REFERENCE_Y: f32 = 100.0;
-const REFERENCE_Z: f32 = 108.883;
+/// CIE standard ε = (6/29)³, where the cube root switches to a linear segment
+pub(super) const EPSILON: f32 = 216.0 / 24389.0;
+/// CIE standard κ = (29/3)³, slope of the linear segment
+pub(super) const KAPPA: f32 = 24389.0 / 27.0;
 
 fn convert_to_pre_lab(value: f32) -> f32 {
-    const TRESHHOLD: f32 = 0.008856;
-
-    match value > TRESHHOLD {
-        true => return value.powf(1.0 / 3.0),
-        false => return 7.787 * value + 16.0 / 116.0,
+    match value > EPSILON {
+        true => value.cbrt(),
+        false => (KAPPA * value + 16.0) / 116.0,
     }
 }
 
 fn convert_to_pre_xyz(value: f32) -> f32 {
-    const TRESHHOLD: f32 = 0.008856;
-
     let pow = value.powi(3);
 
-    match pow > TRESHHOLD {
-        true => return pow,
-        false => return (value - 16.0 / 116.0) / 7.787,
+    match pow > EPSILON {
+        true => pow,
+        false => (116.0 * value - 16.0) / KAPPA,
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ColorCieLab {
     values: Vector3<f32>,
 }
@@ -51,23 +47,37 @@ impl ColorCieLab {
         }
     }
 
+    /// Converts a color relative to the D65 white of sRGB
     pub fn new_from_xyz(xyz: &ColorXyz) -> ColorCieLab {
-        let x = convert_to_pre_lab(xyz.x / REFERENCE_X);
-        let y = convert_to_pre_lab(xyz.y / REFERENCE_Y);
-        let z = convert_to_pre_lab(xyz.z / REFERENCE_Z);
+        Self::new_from_xyz_with_white_point(xyz, WhitePoint::D65)
+    }
+
+    /// Converts a color relative to `white`. Colors seen under another white need to be
+    /// adapted first, see [`super::AdaptationMethod`].
+    pub fn new_from_xyz_with_white_point(xyz: &ColorXyz, white: WhitePoint) -> ColorCieLab {
+        let reference = white.xyz();
+        let x = convert_to_pre_lab(xyz.x / reference.x);
+        let y = convert_to_pre_lab(xyz.y / reference.y);
+        let z = convert_to_pre_lab(xyz.z / reference.z);
 
         ColorCieLab::new(116.0 * y - 16.0, 500.0 * (x - y), 200.0 * (y - z))
     }
 
+    /// Converts to XYZ relative to the D65 white of sRGB
     pub fn as_xyz(&self) -> ColorXyz {
-        let y = (self.l() as f32 + 16.0) / 116.0;
-        let x = self.a() as f32 / 500.0 + y;
-        let z = y - self.b() as f32 / 200.0;
+        self.as_xyz_with_white_point(WhitePoint::D65)
+    }
+
+    pub fn as_xyz_with_white_point(&self, white: WhitePoint) -> ColorXyz {
+        let reference = white.xyz();
+        let y = (self.l() + 16.0) / 116.0;
+        let x = self.a() / 500.0 + y;
+        let z = y - self.b() / 200.0;
 
         ColorXyz {
-            x: convert_to_pre_xyz(x) * REFERENCE_X,
-            y: convert_to_pre_xyz(y) * REFERENCE_Y,
-            z: convert_to_pre_xyz(z) * REFERENCE_Z,
+            x: convert_to_pre_xyz(x) * reference.x,
+            y: convert_to_pre_xyz(y) * reference.y,
+            z: convert_to_pre_xyz(z) * reference.z,
         }
     }
 }
@@ -75,12 +85,13 @@ impl ColorCieLab {
 #[cfg(test)]
 mod test {
 
-    use crate::colors::{ColorCieLab, ColorRgb, ColorXyz};
-    use rand::Rng;
+    use crate::colors::{ColorCieLab, ColorRgb, ColorXyz, WhitePoint};
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
 
     #[test]
     fn convert_from_xyz_to_cielab_and_back_should_be_identity() {
-        let mut rng = rand::thread_rng();
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
 
         for _i in 0..100 {
             let r = rng.gen_range(0, 256) as u8;
@@ -99,4 +110,77 @@ mod test {
             assert_eq!(rgb, converted);
         }
     }
+
+    #[test]
+    fn white_should_be_full_lightness_under_every_white_point() {
+        for white in [
+            WhitePoint::D50,
+            WhitePoint::D55,
+            WhitePoint::D65,
+            WhitePoint::D75,
+            WhitePoint::A,
+            WhitePoint::Custom {
+                x: 90.0,
+                y: 100.0,
+                z: 90.0,
+            },
+        ] {
+            // Act
+            let lab = ColorCieLab::new_from_xyz_with_white_point(&white.xyz(), white);
+            let xyz = lab.as_xyz_with_white_point(white);
+
+            // Assert
+            assert!((lab.l() - 100.0).abs() < 1e-3);
+            assert!(lab.a().abs() < 1e-3 && lab.b().abs() < 1e-3);
+            assert!((xyz.z - white.xyz().z).abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn primaries_should_match_reference_values() {
+        // Arrange
+        let cases = [
+            ((255, 0, 0), (53.2408, 80.0925, 67.2032)),
+            ((0, 255, 0), (87.7347, -86.1827, 83.1793)),
+            ((0, 0, 255), (32.2970, 79.1875, -107.8602)),
+            ((255, 255, 255), (100.0, 0.0, 0.0)),
+            ((0, 0, 0), (0.0, 0.0, 0.0)),
+        ];
+
+        for ((r, g, b), (l, a, b_)) in cases.iter() {
+            // Act
+            let actual =
+                ColorCieLab::new_from_xyz(&ColorXyz::new_from_rgb(&ColorRgb::new(*r, *g, *b)));
+
+            // Assert
+            let delta = [actual.l() - l, actual.a() - a, actual.b() - b_];
+            assert!(delta.iter().all(|d| d.abs() < 2e-3), "{:?}", actual);
+        }
+    }
+
+    #[test]
+    fn linear_segment_should_meet_the_cube_root() {
+        // Arrange
+        let epsilon = 216.0 / 24389.0;
+        let below = ColorXyz {
+            x: 0.0,
+            y: epsilon * 100.0 * 0.9999,
+            z: 0.0,
+        };
+        let above = ColorXyz {
+            x: 0.0,
+            y: epsilon * 100.0 * 1.0001,
+            z: 0.0,
+        };
+
+        // Act
+        let (below, above) = (
+            ColorCieLab::new_from_xyz(&below),
+            ColorCieLab::new_from_xyz(&above),
+        );
+
+        // Assert
+        assert!((below.l() - 8.0).abs() < 1e-2);
+        assert!((above.l() - below.l()).abs() < 1e-2);
+    }
 }
@@ -0,0 +1,214 @@
+use super::{ColorCieLab, ColorCieLuv, ColorLinearRgb, ColorOkLab, ColorRgb, ColorXyz};
+
+/// A color space pixels can be clustered in. Colors are points in a space of
+/// [`Self::DIMENSIONS`] components, compared by euclidean distance and averaged
+/// component by component.
+pub trait ClusterColor: Clone + Send + Sync {
+    /// number of components of a color
+    const DIMENSIONS: usize;
+    /// squared distance between noticeably different colors, about a tenth of the
+    /// lightness range squared. SLICO normalizes by it before clusters have been measured.
+    const INITIAL_MAX_COLOR_DISTANCE: f32;
+
+    /// component `index`, which is below [`Self::DIMENSIONS`]
+    fn component(&self, index: usize) -> f32;
+    /// color made of [`Self::DIMENSIONS`] components
+    fn from_components(components: &[f32]) -> Self;
+
+    fn from_xyz(xyz: &ColorXyz) -> Self;
+    fn to_xyz(&self) -> ColorXyz;
+
+    fn from_rgb(rgb: &ColorRgb) -> Self {
+        Self::from_xyz(&ColorXyz::new_from_rgb(rgb))
+    }
+    fn to_rgb(&self) -> ColorRgb {
+        self.to_xyz().as_rgb()
+    }
+    fn to_lab(&self) -> ColorCieLab {
+        ColorCieLab::new_from_xyz(&self.to_xyz())
+    }
+
+    /// Squared euclidean distance between two colors
+    fn distance2(&self, other: &Self) -> f32 {
+        (0..Self::DIMENSIONS)
+            .map(|i| (self.component(i) - other.component(i)).powi(2))
+            .sum()
+    }
+
+    /// Component-wise mean, accumulated in f64 and in order to stay exact and
+    /// reproducible. `None` if there are no colors.
+    fn mean<'a>(colors: impl IntoIterator<Item = &'a Self>) -> Option<Self>
+    where
+        Self: 'a,
+    {
+        let mut sums = vec![0.0_f64; Self::DIMENSIONS];
+        let mut count = 0;
+        for color in colors {
+            for (i, sum) in sums.iter_mut().enumerate() {
+                *sum += color.component(i) as f64;
+            }
+            count += 1;
+        }
+
+        if count == 0 {
+            return None;
+        }
+
+        let means: Vec<f32> = sums.iter().map(|s| (s / count as f64) as f32).collect();
+        Some(Self::from_components(&means))
+    }
+}
+
+/// Implements [`ClusterColor`] for a color of three `f32` components converting through XYZ
+macro_rules! impl_cluster_color {
+    ($color:ty, $initial_max_color_distance:expr) => {
+        impl ClusterColor for $color {
+            const DIMENSIONS: usize = 3;
+            const INITIAL_MAX_COLOR_DISTANCE: f32 = $initial_max_color_distance;
+
+            fn component(&self, index: usize) -> f32 {
+                self.values()[index]
+            }
+            fn from_components(components: &[f32]) -> Self {
+                Self::new(components[0], components[1], components[2])
+            }
+            fn from_xyz(xyz: &ColorXyz) -> Self {
+                Self::new_from_xyz(xyz)
+            }
+            fn to_xyz(&self) -> ColorXyz {
+                self.as_xyz()
+            }
+        }
+    };
+}
+
+impl_cluster_color!(ColorCieLuv, 100.0);
+impl_cluster_color!(ColorOkLab, 0.01);
+impl_cluster_color!(ColorLinearRgb, 0.01);
+
+impl ClusterColor for ColorCieLab {
+    const DIMENSIONS: usize = 3;
+    const INITIAL_MAX_COLOR_DISTANCE: f32 = 100.0;
+
+    fn component(&self, index: usize) -> f32 {
+        self.values()[index]
+    }
+    fn from_components(components: &[f32]) -> Self {
+        Self::new(components[0], components[1], components[2])
+    }
+    fn from_xyz(xyz: &ColorXyz) -> Self {
+        Self::new_from_xyz(xyz)
+    }
+    fn to_xyz(&self) -> ColorXyz {
+        self.as_xyz()
+    }
+    fn to_lab(&self) -> ColorCieLab {
+        self.clone()
+    }
+}
+
+impl ClusterColor for ColorXyz {
+    const DIMENSIONS: usize = 3;
+    const INITIAL_MAX_COLOR_DISTANCE: f32 = 100.0;
+
+    fn component(&self, index: usize) -> f32 {
+        [self.x, self.y, self.z][index]
+    }
+    fn from_components(components: &[f32]) -> Self {
+        ColorXyz {
+            x: components[0],
+            y: components[1],
+            z: components[2],
+        }
+    }
+    fn from_xyz(xyz: &ColorXyz) -> Self {
+        xyz.clone()
+    }
+    fn to_xyz(&self) -> ColorXyz {
+        self.clone()
+    }
+}
+
+/// Clusters on the 8 bit sRGB encoded components, means are rounded to the closest color
+impl ClusterColor for ColorRgb {
+    const DIMENSIONS: usize = 3;
+    const INITIAL_MAX_COLOR_DISTANCE: f32 = 625.0;
+
+    fn component(&self, index: usize) -> f32 {
+        let (r, g, b) = self.values();
+        [r, g, b][index] as f32
+    }
+    fn from_components(components: &[f32]) -> Self {
+        let quantize = |v: f32| v.round().clamp(0.0, 255.0) as u8;
+        ColorRgb::new(
+            quantize(components[0]),
+            quantize(components[1]),
+            quantize(components[2]),
+        )
+    }
+    fn from_xyz(xyz: &ColorXyz) -> Self {
+        xyz.as_rgb()
+    }
+    fn to_xyz(&self) -> ColorXyz {
+        ColorXyz::new_from_rgb(self)
+    }
+    fn from_rgb(rgb: &ColorRgb) -> Self {
+        rgb.clone()
+    }
+    fn to_rgb(&self) -> ColorRgb {
+        self.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::{ClusterColor, ColorCieLab, ColorOkLab, ColorRgb};
+
+    #[test]
+    fn mean_should_average_every_component() {
+        // Arrange
+        let colors = [
+            ColorCieLab::new(10.0, -20.0, 30.0),
+            ColorCieLab::new(30.0, 20.0, -10.0),
+        ];
+
+        // Act
+        let mean = ColorCieLab::mean(&colors).unwrap();
+        let empty = ColorCieLab::mean(&[]);
+
+        // Assert
+        assert_eq!([20.0, 0.0, 10.0], [mean.l(), mean.a(), mean.b()]);
+        assert!(empty.is_none());
+    }
+
+    #[test]
+    fn distance_should_be_euclidean() {
+        // Arrange
+        let a = ColorOkLab::new(0.5, 0.1, 0.0);
+        let b = ColorOkLab::new(0.5, 0.0, 0.1);
+
+        // Act
+        let distance = a.distance2(&b);
+
+        // Assert
+        assert!((distance - 0.02).abs() < 1e-6);
+    }
+
+    #[test]
+    fn rgb_should_round_trip_through_every_space() {
+        // Arrange
+        let rgb = ColorRgb::new(200, 120, 30);
+
+        // Act
+        let converted = [
+            ColorCieLab::from_rgb(&rgb).to_rgb(),
+            ColorOkLab::from_rgb(&rgb).to_rgb(),
+            ColorRgb::from_rgb(&rgb).to_rgb(),
+        ];
+
+        // Assert
+        for color in converted {
+            assert_eq!(rgb, color);
+        }
+    }
+}
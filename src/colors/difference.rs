@@ -0,0 +1,249 @@
+use super::ColorCieLab;
+
+/// Perceptual difference ΔE between two CIE Lab colors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorDifference {
+    /// CIE 1976, the euclidean distance in CIE Lab
+    Cie76,
+    /// CIE 1994 with the weights for graphic arts
+    Cie94GraphicArts,
+    /// CIE 1994 with the weights for textiles
+    Cie94Textiles,
+    /// CIEDE2000, which corrects CIE 1994 for blues and neutral colors
+    Ciede2000,
+    /// CMC l:c, 2:1 is commonly used for acceptability and 1:1 for perceptibility
+    Cmc { lightness: f32, chroma: f32 },
+}
+
+impl ColorDifference {
+    /// Difference of `sample` from `reference`. CIE 1994 and CMC are not symmetric,
+    /// they weigh the difference by the chroma and hue of the reference.
+    pub fn delta_e(&self, reference: &ColorCieLab, sample: &ColorCieLab) -> f32 {
+        let (reference, sample) = (lab(reference), lab(sample));
+
+        let delta_e = match self {
+            ColorDifference::Cie76 => cie76(reference, sample),
+            ColorDifference::Cie94GraphicArts => cie94(reference, sample, 1.0, 0.045, 0.015),
+            ColorDifference::Cie94Textiles => cie94(reference, sample, 2.0, 0.048, 0.014),
+            ColorDifference::Ciede2000 => ciede2000(reference, sample),
+            ColorDifference::Cmc { lightness, chroma } => {
+                cmc(reference, sample, *lightness as f64, *chroma as f64)
+            }
+        };
+
+        delta_e as f32
+    }
+}
+
+/// L, a and b in double precision, so the trigonometry doesn't lose accuracy
+type Lab = (f64, f64, f64);
+
+fn lab(color: &ColorCieLab) -> Lab {
+    (color.l() as f64, color.a() as f64, color.b() as f64)
+}
+
+/// Hue angle in degrees between 0 and 360, 0 for neutral colors
+fn hue(a: f64, b: f64) -> f64 {
+    match a == 0.0 && b == 0.0 {
+        true => 0.0,
+        false => b.atan2(a).to_degrees().rem_euclid(360.0),
+    }
+}
+
+/// Squared hue difference, what is left of the a, b distance besides the chroma difference
+fn hue_difference2((_, a1, b1): Lab, (_, a2, b2): Lab) -> f64 {
+    let chroma_difference = a1.hypot(b1) - a2.hypot(b2);
+    ((a1 - a2).powi(2) + (b1 - b2).powi(2) - chroma_difference.powi(2)).max(0.0)
+}
+
+fn cie76((l1, a1, b1): Lab, (l2, a2, b2): Lab) -> f64 {
+    ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+}
+
+fn cie94(reference: Lab, sample: Lab, k_l: f64, k_1: f64, k_2: f64) -> f64 {
+    let c1 = reference.1.hypot(reference.2);
+    let c2 = sample.1.hypot(sample.2);
+
+    let s_c = 1.0 + k_1 * c1;
+    let s_h = 1.0 + k_2 * c1;
+
+    (((reference.0 - sample.0) / k_l).powi(2)
+        + ((c1 - c2) / s_c).powi(2)
+        + hue_difference2(reference, sample) / s_h.powi(2))
+    .sqrt()
+}
+
+/// CIEDE2000 as given by Sharma, Wu and Dalal, with all parametric factors set to 1
+fn ciede2000((l1, a1, b1): Lab, (l2, a2, b2): Lab) -> f64 {
+    let pow25_7 = 25.0_f64.powi(7);
+
+    let c_mean = (a1.hypot(b1) + a2.hypot(b2)) / 2.0;
+    let g = 0.5 * (1.0 - (c_mean.powi(7) / (c_mean.powi(7) + pow25_7)).sqrt());
+    let (a1, a2) = ((1.0 + g) * a1, (1.0 + g) * a2);
+
+    let (c1, c2) = (a1.hypot(b1), a2.hypot(b2));
+    let (h1, h2) = (hue(a1, b1), hue(a2, b2));
+    let neutral = c1 * c2 == 0.0;
+
+    let delta_l = l2 - l1;
+    let delta_c = c2 - c1;
+    let delta_h = match h2 - h1 {
+        _ if neutral => 0.0,
+        d if d > 180.0 => d - 360.0,
+        d if d < -180.0 => d + 360.0,
+        d => d,
+    };
+    let delta_h = 2.0 * (c1 * c2).sqrt() * (delta_h / 2.0).to_radians().sin();
+
+    let l_mean = (l1 + l2) / 2.0;
+    let c_mean = (c1 + c2) / 2.0;
+    let h_mean = match (h1 - h2).abs() {
+        _ if neutral => h1 + h2,
+        d if d <= 180.0 => (h1 + h2) / 2.0,
+        _ if h1 + h2 < 360.0 => (h1 + h2 + 360.0) / 2.0,
+        _ => (h1 + h2 - 360.0) / 2.0,
+    };
+
+    let cos = |degrees: f64| degrees.to_radians().cos();
+    let t =
+        1.0 - 0.17 * cos(h_mean - 30.0) + 0.24 * cos(2.0 * h_mean) + 0.32 * cos(3.0 * h_mean + 6.0)
+            - 0.20 * cos(4.0 * h_mean - 63.0);
+    let delta_theta = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+    let r_c = 2.0 * (c_mean.powi(7) / (c_mean.powi(7) + pow25_7)).sqrt();
+    let s_l = 1.0 + 0.015 * (l_mean - 50.0).powi(2) / (20.0 + (l_mean - 50.0).powi(2)).sqrt();
+    let s_c = 1.0 + 0.045 * c_mean;
+    let s_h = 1.0 + 0.015 * c_mean * t;
+    let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+    let (l, c, h) = (delta_l / s_l, delta_c / s_c, delta_h / s_h);
+    (l * l + c * c + h * h + r_t * c * h).sqrt()
+}
+
+fn cmc(reference: Lab, sample: Lab, lightness: f64, chroma: f64) -> f64 {
+    let (l1, a1, b1) = reference;
+    let c1 = a1.hypot(b1);
+    let c2 = sample.1.hypot(sample.2);
+    let h1 = hue(a1, b1);
+
+    let s_l = match l1 < 16.0 {
+        true => 0.511,
+        false => 0.040975 * l1 / (1.0 + 0.01765 * l1),
+    };
+    let s_c = 0.0638 * c1 / (1.0 + 0.0131 * c1) + 0.638;
+    let f = (c1.powi(4) / (c1.powi(4) + 1900.0)).sqrt();
+    let t = match (164.0..=345.0).contains(&h1) {
+        true => 0.56 + (0.2 * (h1 + 168.0).to_radians().cos()).abs(),
+        false => 0.36 + (0.4 * (h1 + 35.0).to_radians().cos()).abs(),
+    };
+    let s_h = s_c * (f * t + 1.0 - f);
+
+    (((l1 - sample.0) / (lightness * s_l)).powi(2)
+        + ((c1 - c2) / (chroma * s_c)).powi(2)
+        + hue_difference2(reference, sample) / s_h.powi(2))
+    .sqrt()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::{ColorCieLab, ColorDifference};
+
+    const DIFFERENCES: [ColorDifference; 5] = [
+        ColorDifference::Cie76,
+        ColorDifference::Cie94GraphicArts,
+        ColorDifference::Cie94Textiles,
+        ColorDifference::Ciede2000,
+        ColorDifference::Cmc {
+            lightness: 2.0,
+            chroma: 1.0,
+        },
+    ];
+
+    #[test]
+    fn equal_colors_should_not_differ() {
+        // Arrange
+        let color = ColorCieLab::new(50.0, 20.0, -30.0);
+
+        // Act & Assert
+        for difference in DIFFERENCES.iter() {
+            assert_eq!(0.0, difference.delta_e(&color, &color));
+        }
+    }
+
+    #[test]
+    fn ciede2000_should_match_sharma_test_data() {
+        // Arrange, pairs and ΔE of the test data by Sharma, Wu and Dalal
+        let pairs = [
+            ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425),
+            ((50.0, 0.0, 0.0), (50.0, -1.0, 2.0), 2.3669),
+            ((50.0, 2.49, -0.001), (50.0, -2.49, 0.0011), 7.2195),
+            ((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492),
+            (
+                (60.2574, -34.0099, 36.2677),
+                (60.4626, -34.1751, 39.4387),
+                1.2644,
+            ),
+            ((2.0776, 0.0795, -1.135), (0.9033, -0.0636, -0.5514), 0.9082),
+        ];
+
+        for ((l1, a1, b1), (l2, a2, b2), expected) in pairs.iter() {
+            let reference = ColorCieLab::new(*l1, *a1, *b1);
+            let sample = ColorCieLab::new(*l2, *a2, *b2);
+
+            // Act
+            let forward = ColorDifference::Ciede2000.delta_e(&reference, &sample);
+            let backward = ColorDifference::Ciede2000.delta_e(&sample, &reference);
+
+            // Assert
+            assert!(
+                (forward - expected).abs() < 1e-4,
+                "{} != {}",
+                forward,
+                expected
+            );
+            assert!((forward - backward).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn cie76_should_be_euclidean() {
+        // Act
+        let delta_e = ColorDifference::Cie76.delta_e(
+            &ColorCieLab::new(50.0, 0.0, 0.0),
+            &ColorCieLab::new(53.0, 4.0, 0.0),
+        );
+
+        // Assert
+        assert!((delta_e - 5.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn lightness_differences_should_be_weighted() {
+        // Arrange
+        let reference = ColorCieLab::new(50.0, 0.0, 0.0);
+        let sample = ColorCieLab::new(54.0, 0.0, 0.0);
+
+        // Act
+        let graphic_arts = ColorDifference::Cie94GraphicArts.delta_e(&reference, &sample);
+        let textiles = ColorDifference::Cie94Textiles.delta_e(&reference, &sample);
+        let cmc = DIFFERENCES[4].delta_e(&reference, &sample);
+
+        // Assert
+        let s_l = 0.040975 * 50.0 / (1.0 + 0.01765 * 50.0);
+        assert!((graphic_arts - 4.0).abs() < 1e-5);
+        assert!((textiles - 2.0).abs() < 1e-5);
+        assert!((cmc - 4.0 / (2.0 * s_l)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn cie94_should_weigh_chroma_of_the_reference() {
+        // Arrange
+        let reference = ColorCieLab::new(50.0, 40.0, 0.0);
+        let sample = ColorCieLab::new(50.0, 50.0, 0.0);
+
+        // Act
+        let delta_e = ColorDifference::Cie94GraphicArts.delta_e(&reference, &sample);
+
+        // Assert
+        assert!((delta_e - 10.0 / (1.0 + 0.045 * 40.0)).abs() < 1e-5);
+    }
+}
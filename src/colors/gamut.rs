@@ -0,0 +1,155 @@
+use super::{ColorCieLab, ColorCieLch, ColorXyz};
+
+/// Linear sRGB components may exceed 0 and 1 by this much and still count as in gamut,
+/// which absorbs the rounding error of converting between color spaces
+pub const GAMUT_TOLERANCE: f32 = 1e-4;
+
+/// Halvings of the chroma range [`GamutMapping::ChromaReduction`] searches, enough
+/// to get well below 8 bit quantization
+const CHROMA_SEARCH_STEPS: usize = 24;
+
+/// How colors outside of the sRGB gamut are brought into it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GamutMapping {
+    /// clamps every sRGB component to 0 and 1, which can shift hue and lightness
+    Clip,
+    /// lowers the CIE LCh chroma until the color fits, keeping lightness and hue.
+    /// Lightness above white or below black is clipped.
+    #[default]
+    ChromaReduction,
+}
+
+impl GamutMapping {
+    /// sRGB encoded components of a color, between 0 and 1
+    pub fn map(&self, xyz: &ColorXyz) -> (f32, f32, f32) {
+        let (r, g, b) = match self {
+            GamutMapping::Clip => xyz.as_srgb_f32(),
+            GamutMapping::ChromaReduction => Self::reduce_chroma(xyz).as_srgb_f32(),
+        };
+        let clip = |v: f32| v.clamp(0.0, 1.0);
+
+        (clip(r), clip(g), clip(b))
+    }
+
+    /// The most saturated color inside the gamut with the lightness and hue of `xyz`
+    fn reduce_chroma(xyz: &ColorXyz) -> ColorXyz {
+        if xyz.is_in_gamut(GAMUT_TOLERANCE) {
+            return xyz.clone();
+        }
+
+        let lch = ColorCieLch::new_from_xyz(xyz);
+        if lch.l() >= 100.0 || lch.l() <= 0.0 {
+            return ColorCieLab::new(lch.l().clamp(0.0, 100.0), 0.0, 0.0).as_xyz();
+        }
+
+        // grays are inside the gamut, so the boundary lies between no chroma and the chroma of xyz
+        let with_chroma = |c: f32| ColorCieLch::new(lch.l(), c, lch.h()).as_xyz();
+        let (mut inside, mut outside) = (0.0, lch.c());
+        for _ in 0..CHROMA_SEARCH_STEPS {
+            let chroma = (inside + outside) / 2.0;
+            match with_chroma(chroma).is_in_gamut(GAMUT_TOLERANCE) {
+                true => inside = chroma,
+                false => outside = chroma,
+            }
+        }
+
+        with_chroma(inside)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::{
+        ColorCieLab, ColorCieLch, ColorRgb, ColorXyz, GamutMapping, GAMUT_TOLERANCE,
+    };
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn colors_inside_the_gamut_should_not_change() {
+        let mut rng = ChaCha8Rng::seed_from_u64(8);
+
+        for _i in 0..100 {
+            // Arrange
+            let rgb = ColorRgb::new(rng.gen(), rng.gen(), rng.gen());
+            let xyz = ColorXyz::new_from_rgb(&rgb);
+
+            // Act
+            let clipped = xyz.as_rgb_with(GamutMapping::Clip);
+            let reduced = xyz.as_rgb_with(GamutMapping::ChromaReduction);
+
+            // Assert
+            assert!(xyz.is_in_gamut(GAMUT_TOLERANCE));
+            assert_eq!(rgb, clipped);
+            assert_eq!(rgb, reduced);
+        }
+    }
+
+    #[test]
+    fn saturated_lab_colors_should_be_outside_the_gamut() {
+        // Arrange
+        let colors = [
+            ColorCieLab::new(50.0, 0.0, -120.0),
+            ColorCieLab::new(60.0, 120.0, 0.0),
+            ColorCieLab::new(90.0, -80.0, 90.0),
+        ];
+
+        for lab in colors.iter() {
+            // Act
+            let in_gamut = lab.as_xyz().is_in_gamut(GAMUT_TOLERANCE);
+
+            // Assert
+            assert!(!in_gamut, "{:?}", lab);
+        }
+    }
+
+    #[test]
+    fn chroma_reduction_should_keep_lightness_and_hue() {
+        // Arrange
+        let colors = [
+            ColorCieLab::new(50.0, 0.0, -120.0),
+            ColorCieLab::new(60.0, 120.0, 0.0),
+            ColorCieLab::new(90.0, -80.0, 90.0),
+        ];
+
+        for lab in colors.iter() {
+            let original = ColorCieLch::new_from_lab(lab);
+
+            // Act
+            let rgb = lab.as_xyz().as_rgb_with(GamutMapping::ChromaReduction);
+
+            // Assert
+            let mapped = ColorCieLch::new_from_xyz(&ColorXyz::new_from_rgb(&rgb));
+            assert!((mapped.l() - original.l()).abs() < 0.5, "{:?}", mapped);
+            assert!((mapped.h() - original.h()).abs() < 1.5, "{:?}", mapped);
+            assert!(mapped.c() < original.c());
+        }
+    }
+
+    #[test]
+    fn lightness_beyond_white_should_map_to_white() {
+        // Arrange
+        let lab = ColorCieLab::new(110.0, 20.0, 20.0);
+
+        // Act
+        let rgb = lab.as_xyz().as_rgb_with(GamutMapping::ChromaReduction);
+
+        // Assert
+        assert_eq!(ColorRgb::new(255, 255, 255), rgb);
+    }
+
+    #[test]
+    fn clip_should_clamp_every_component() {
+        // Arrange
+        let xyz = ColorXyz::new_from_linear_rgb(1.5, -0.2, 0.5);
+
+        // Act
+        let (r, g, b) = GamutMapping::Clip.map(&xyz);
+
+        // Assert
+        assert_eq!((1.0, 0.0), (r, g));
+        assert!(b > 0.0 && b < 1.0);
+        assert!(!xyz.is_in_gamut(0.1));
+        assert!(xyz.is_in_gamut(0.5));
+    }
+}
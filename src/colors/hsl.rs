@@ -0,0 +1,95 @@
+use super::hsv::{from_hue_chroma, hue_max_min};
+use super::ColorXyz;
+use cgmath::Vector3;
+
+/// Hue, saturation and lightness of sRGB encoded components
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorHsl {
+    values: Vector3<f32>,
+}
+
+impl ColorHsl {
+    /// hue angle in degrees
+    pub fn h(&self) -> f32 {
+        self.values[0]
+    }
+    /// saturation between 0 and 1
+    pub fn s(&self) -> f32 {
+        self.values[1]
+    }
+    /// lightness between 0 and 1
+    pub fn l(&self) -> f32 {
+        self.values[2]
+    }
+    pub fn values(&self) -> &Vector3<f32> {
+        &self.values
+    }
+
+    pub const fn new(h: f32, s: f32, l: f32) -> ColorHsl {
+        ColorHsl {
+            values: Vector3::new(h, s, l),
+        }
+    }
+
+    pub fn new_from_srgb_f32(r: f32, g: f32, b: f32) -> ColorHsl {
+        let (hue, max, min) = hue_max_min(r, g, b);
+        let lightness = (max + min) / 2.0;
+        let saturation = match lightness == 0.0 || lightness == 1.0 {
+            true => 0.0,
+            false => (max - min) / (1.0 - (2.0 * lightness - 1.0).abs()),
+        };
+
+        ColorHsl::new(hue, saturation, lightness)
+    }
+
+    /// sRGB encoded components between 0 and 1
+    pub fn as_srgb_f32(&self) -> (f32, f32, f32) {
+        let chroma = (1.0 - (2.0 * self.l() - 1.0).abs()) * self.s();
+        from_hue_chroma(self.h(), chroma, self.l() - chroma / 2.0)
+    }
+
+    pub fn new_from_xyz(xyz: &ColorXyz) -> ColorHsl {
+        let (r, g, b) = xyz.as_srgb_f32();
+        Self::new_from_srgb_f32(r, g, b)
+    }
+
+    pub fn as_xyz(&self) -> ColorXyz {
+        let (r, g, b) = self.as_srgb_f32();
+        ColorXyz::new_from_srgb_f32(r, g, b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::{ColorHsl, ColorRgb, ColorXyz};
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn teal_should_match_reference_values() {
+        // Act
+        let hsl = ColorHsl::new_from_srgb_f32(0.0, 0.5, 0.5);
+
+        // Assert
+        assert!((hsl.h() - 180.0).abs() < 1e-4);
+        assert!((hsl.s() - 1.0).abs() < 1e-6);
+        assert!((hsl.l() - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn convert_from_rgb_to_hsl_and_back_should_be_identity() {
+        let mut rng = ChaCha8Rng::seed_from_u64(6);
+
+        for _i in 0..100 {
+            // Arrange
+            let rgb = ColorRgb::new(rng.gen(), rng.gen(), rng.gen());
+
+            // Act
+            let hsl = ColorHsl::new_from_xyz(&ColorXyz::new_from_rgb(&rgb));
+            let converted = hsl.as_xyz().as_rgb();
+
+            // Assert
+            assert_eq!(rgb, converted);
+        }
+    }
+}
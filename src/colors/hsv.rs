@@ -0,0 +1,126 @@
+use super::ColorXyz;
+use cgmath::Vector3;
+
+/// Hue in degrees, largest and smallest component of sRGB encoded components
+pub(super) fn hue_max_min(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let hue = match delta {
+        0.0 => 0.0,
+        d if max == r => 60.0 * ((g - b) / d).rem_euclid(6.0),
+        d if max == g => 60.0 * ((b - r) / d + 2.0),
+        d => 60.0 * ((r - g) / d + 4.0),
+    };
+
+    (hue, max, min)
+}
+
+/// sRGB encoded components of a hue in degrees, chroma and the smallest component
+pub(super) fn from_hue_chroma(hue: f32, chroma: f32, min: f32) -> (f32, f32, f32) {
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    (r + min, g + min, b + min)
+}
+
+/// Hue, saturation and value of sRGB encoded components
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorHsv {
+    values: Vector3<f32>,
+}
+
+impl ColorHsv {
+    /// hue angle in degrees
+    pub fn h(&self) -> f32 {
+        self.values[0]
+    }
+    /// saturation between 0 and 1
+    pub fn s(&self) -> f32 {
+        self.values[1]
+    }
+    /// value between 0 and 1
+    pub fn v(&self) -> f32 {
+        self.values[2]
+    }
+    pub fn values(&self) -> &Vector3<f32> {
+        &self.values
+    }
+
+    pub const fn new(h: f32, s: f32, v: f32) -> ColorHsv {
+        ColorHsv {
+            values: Vector3::new(h, s, v),
+        }
+    }
+
+    pub fn new_from_srgb_f32(r: f32, g: f32, b: f32) -> ColorHsv {
+        let (hue, max, min) = hue_max_min(r, g, b);
+        let saturation = match max == 0.0 {
+            true => 0.0,
+            false => (max - min) / max,
+        };
+
+        ColorHsv::new(hue, saturation, max)
+    }
+
+    /// sRGB encoded components between 0 and 1
+    pub fn as_srgb_f32(&self) -> (f32, f32, f32) {
+        let chroma = self.v() * self.s();
+        from_hue_chroma(self.h(), chroma, self.v() - chroma)
+    }
+
+    pub fn new_from_xyz(xyz: &ColorXyz) -> ColorHsv {
+        let (r, g, b) = xyz.as_srgb_f32();
+        Self::new_from_srgb_f32(r, g, b)
+    }
+
+    pub fn as_xyz(&self) -> ColorXyz {
+        let (r, g, b) = self.as_srgb_f32();
+        ColorXyz::new_from_srgb_f32(r, g, b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::{ColorHsv, ColorRgb, ColorXyz};
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn orange_should_match_reference_values() {
+        // Act
+        let hsv = ColorHsv::new_from_srgb_f32(1.0, 0.5, 0.0);
+
+        // Assert
+        assert!((hsv.h() - 30.0).abs() < 1e-4);
+        assert!((hsv.s() - 1.0).abs() < 1e-6);
+        assert!((hsv.v() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn convert_from_rgb_to_hsv_and_back_should_be_identity() {
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+
+        for _i in 0..100 {
+            // Arrange
+            let rgb = ColorRgb::new(rng.gen(), rng.gen(), rng.gen());
+
+            // Act
+            let hsv = ColorHsv::new_from_xyz(&ColorXyz::new_from_rgb(&rgb));
+            let converted = hsv.as_xyz().as_rgb();
+
+            // Assert
+            assert_eq!(rgb, converted);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use super::{ColorCieLab, ColorXyz};
+use cgmath::Vector3;
+
+/// Chroma and hue in degrees, between 0 and 360, of a pair of opponent axes
+pub(super) fn to_polar(a: f32, b: f32) -> (f32, f32) {
+    let hue = b.atan2(a).to_degrees();
+
+    (a.hypot(b), if hue < 0.0 { hue + 360.0 } else { hue })
+}
+
+/// Opponent axes of a chroma and hue in degrees
+pub(super) fn from_polar(chroma: f32, hue: f32) -> (f32, f32) {
+    let (sin, cos) = hue.to_radians().sin_cos();
+
+    (chroma * cos, chroma * sin)
+}
+
+/// CIE LCh(ab), the polar form of CIE Lab
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorCieLch {
+    values: Vector3<f32>,
+}
+
+impl ColorCieLch {
+    pub fn l(&self) -> f32 {
+        self.values[0]
+    }
+    pub fn c(&self) -> f32 {
+        self.values[1]
+    }
+    /// hue angle in degrees
+    pub fn h(&self) -> f32 {
+        self.values[2]
+    }
+    pub fn values(&self) -> &Vector3<f32> {
+        &self.values
+    }
+
+    pub const fn new(l: f32, c: f32, h: f32) -> ColorCieLch {
+        ColorCieLch {
+            values: Vector3::new(l, c, h),
+        }
+    }
+
+    pub fn new_from_lab(lab: &ColorCieLab) -> ColorCieLch {
+        let (c, h) = to_polar(lab.a(), lab.b());
+        ColorCieLch::new(lab.l(), c, h)
+    }
+
+    pub fn as_lab(&self) -> ColorCieLab {
+        let (a, b) = from_polar(self.c(), self.h());
+        ColorCieLab::new(self.l(), a, b)
+    }
+
+    pub fn new_from_xyz(xyz: &ColorXyz) -> ColorCieLch {
+        Self::new_from_lab(&ColorCieLab::new_from_xyz(xyz))
+    }
+
+    pub fn as_xyz(&self) -> ColorXyz {
+        self.as_lab().as_xyz()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::{ColorCieLch, ColorRgb, ColorXyz};
+
+    #[test]
+    fn red_should_match_reference_values() {
+        // Arrange
+        let red = ColorXyz::new_from_rgb(&ColorRgb::new(255, 0, 0));
+
+        // Act
+        let lch = ColorCieLch::new_from_xyz(&red);
+
+        // Assert
+        assert!((lch.l() - 53.2408).abs() < 2e-3);
+        assert!((lch.c() - 104.5518).abs() < 2e-3);
+        assert!((lch.h() - 39.9990).abs() < 2e-3);
+        assert_eq!(ColorRgb::new(255, 0, 0), lch.as_xyz().as_rgb());
+    }
+}
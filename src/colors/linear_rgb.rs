@@ -0,0 +1,58 @@
+use super::ColorXyz;
+use cgmath::Vector3;
+
+/// RGB with sRGB primaries and without the sRGB transfer function, so components are
+/// proportional to light. 1 is the white point, brighter values are allowed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorLinearRgb {
+    values: Vector3<f32>,
+}
+
+impl ColorLinearRgb {
+    pub fn r(&self) -> f32 {
+        self.values[0]
+    }
+    pub fn g(&self) -> f32 {
+        self.values[1]
+    }
+    pub fn b(&self) -> f32 {
+        self.values[2]
+    }
+    pub fn values(&self) -> &Vector3<f32> {
+        &self.values
+    }
+
+    pub const fn new(r: f32, g: f32, b: f32) -> ColorLinearRgb {
+        ColorLinearRgb {
+            values: Vector3::new(r, g, b),
+        }
+    }
+
+    pub fn new_from_xyz(xyz: &ColorXyz) -> ColorLinearRgb {
+        xyz.as_linear_rgb()
+    }
+
+    pub fn as_xyz(&self) -> ColorXyz {
+        ColorXyz::new_from_linear_rgb(self.r(), self.g(), self.b())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::{ColorLinearRgb, ColorRgb, ColorXyz};
+
+    #[test]
+    fn mid_gray_should_be_about_a_fifth_of_white() {
+        // Arrange
+        let gray = ColorXyz::new_from_rgb(&ColorRgb::new(128, 128, 128));
+
+        // Act
+        let linear = ColorLinearRgb::new_from_xyz(&gray);
+
+        // Assert
+        for v in [linear.r(), linear.g(), linear.b()] {
+            assert!((v - 0.21586).abs() < 1e-4, "{}", v);
+        }
+        assert_eq!(ColorRgb::new(128, 128, 128), linear.as_xyz().as_rgb());
+    }
+}
@@ -0,0 +1,118 @@
+use super::cie_lab::{EPSILON, KAPPA};
+use super::{ColorXyz, WhitePoint};
+use cgmath::Vector3;
+
+/// Chromaticity coordinates u' and v' of a color
+fn chromaticity(xyz: &ColorXyz) -> (f32, f32) {
+    let denominator = xyz.x + 15.0 * xyz.y + 3.0 * xyz.z;
+    if denominator == 0.0 {
+        return (0.0, 0.0);
+    }
+
+    (4.0 * xyz.x / denominator, 9.0 * xyz.y / denominator)
+}
+
+/// CIE 1976 L*u*v*, relative to the D65 white of sRGB
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorCieLuv {
+    values: Vector3<f32>,
+}
+
+impl ColorCieLuv {
+    pub fn l(&self) -> f32 {
+        self.values[0]
+    }
+    pub fn u(&self) -> f32 {
+        self.values[1]
+    }
+    pub fn v(&self) -> f32 {
+        self.values[2]
+    }
+    pub fn values(&self) -> &Vector3<f32> {
+        &self.values
+    }
+
+    pub const fn new(l: f32, u: f32, v: f32) -> ColorCieLuv {
+        ColorCieLuv {
+            values: Vector3::new(l, u, v),
+        }
+    }
+
+    pub fn new_from_xyz(xyz: &ColorXyz) -> ColorCieLuv {
+        let white = WhitePoint::D65.xyz();
+        let y = xyz.y / white.y;
+        let l = match y > EPSILON {
+            true => 116.0 * y.cbrt() - 16.0,
+            false => KAPPA * y,
+        };
+
+        let (u, v) = chromaticity(xyz);
+        let (white_u, white_v) = chromaticity(&white);
+
+        ColorCieLuv::new(l, 13.0 * l * (u - white_u), 13.0 * l * (v - white_v))
+    }
+
+    pub fn as_xyz(&self) -> ColorXyz {
+        if self.l() <= 0.0 {
+            return ColorXyz {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            };
+        }
+
+        let white = WhitePoint::D65.xyz();
+        let (white_u, white_v) = chromaticity(&white);
+        let u = self.u() / (13.0 * self.l()) + white_u;
+        let v = self.v() / (13.0 * self.l()) + white_v;
+
+        let y = match self.l() > KAPPA * EPSILON {
+            true => ((self.l() + 16.0) / 116.0).powi(3),
+            false => self.l() / KAPPA,
+        } * white.y;
+
+        ColorXyz {
+            x: y * 9.0 * u / (4.0 * v),
+            y,
+            z: y * (12.0 - 3.0 * u - 20.0 * v) / (4.0 * v),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::{ColorCieLuv, ColorRgb, ColorXyz};
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn red_should_match_reference_values() {
+        // Arrange
+        let red = ColorXyz::new_from_rgb(&ColorRgb::new(255, 0, 0));
+
+        // Act
+        let luv = ColorCieLuv::new_from_xyz(&red);
+
+        // Assert
+        assert!((luv.l() - 53.2408).abs() < 2e-3);
+        assert!((luv.u() - 175.0151).abs() < 1e-2);
+        assert!((luv.v() - 37.7564).abs() < 1e-2);
+    }
+
+    #[test]
+    fn convert_from_rgb_to_luv_and_back_should_be_identity() {
+        let mut rng = ChaCha8Rng::seed_from_u64(4);
+
+        for _i in 0..100 {
+            // Arrange
+            let rgb = ColorRgb::new(rng.gen(), rng.gen(), rng.gen());
+
+            // Act
+            let luv = ColorCieLuv::new_from_xyz(&ColorXyz::new_from_rgb(&rgb));
+            let converted = luv.as_xyz().as_rgb();
+
+            // Assert
+            assert_eq!(rgb, converted);
+        }
+    }
+}
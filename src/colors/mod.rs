@@ -1,14 +1,164 @@
 pub use self::rgb::ColorRgb;
 mod rgb;
 
+pub use self::linear_rgb::ColorLinearRgb;
+mod linear_rgb;
+
 pub use self::xyz::ColorXyz;
 mod xyz;
 
 pub use self::cie_lab::ColorCieLab;
 mod cie_lab;
 
+pub use self::lch::ColorCieLch;
+mod lch;
+
+pub use self::luv::ColorCieLuv;
+mod luv;
+
+pub use self::oklab::ColorOkLab;
+mod oklab;
+
+pub use self::oklch::ColorOkLch;
+mod oklch;
+
+pub use self::hsv::ColorHsv;
+mod hsv;
+
+pub use self::hsl::ColorHsl;
+mod hsl;
+
+pub use self::ycbcr::{ColorYCbCr, YCbCrStandard};
+mod ycbcr;
+
+pub use self::white_point::WhitePoint;
+mod white_point;
+
+pub use self::adaptation::AdaptationMethod;
+mod adaptation;
+
+pub use self::cluster_color::ClusterColor;
+mod cluster_color;
+
+pub use self::difference::ColorDifference;
+mod difference;
+
+pub use self::gamut::{GamutMapping, GAMUT_TOLERANCE};
+mod gamut;
+
+/// A color in any of the supported spaces, every space converts through XYZ
+#[derive(Debug, Clone, PartialEq)]
 pub enum Color {
     Rgb(ColorRgb),
+    LinearRgb(ColorLinearRgb),
     Xyz(ColorXyz),
     CieLab(ColorCieLab),
+    CieLch(ColorCieLch),
+    CieLuv(ColorCieLuv),
+    OkLab(ColorOkLab),
+    OkLch(ColorOkLch),
+    Hsv(ColorHsv),
+    Hsl(ColorHsl),
+    YCbCr(ColorYCbCr),
+}
+
+impl Color {
+    pub fn to_xyz(&self) -> ColorXyz {
+        match self {
+            Color::Rgb(rgb) => ColorXyz::new_from_rgb(rgb),
+            Color::LinearRgb(rgb) => rgb.as_xyz(),
+            Color::Xyz(xyz) => xyz.clone(),
+            Color::CieLab(lab) => lab.as_xyz(),
+            Color::CieLch(lch) => lch.as_xyz(),
+            Color::CieLuv(luv) => luv.as_xyz(),
+            Color::OkLab(lab) => lab.as_xyz(),
+            Color::OkLch(lch) => lch.as_xyz(),
+            Color::Hsv(hsv) => hsv.as_xyz(),
+            Color::Hsl(hsl) => hsl.as_xyz(),
+            Color::YCbCr(ycbcr) => ycbcr.as_xyz(),
+        }
+    }
+
+    pub fn to_rgb(&self) -> ColorRgb {
+        self.to_rgb_with(GamutMapping::default())
+    }
+
+    pub fn to_rgb_with(&self, mapping: GamutMapping) -> ColorRgb {
+        match self {
+            Color::Rgb(rgb) => rgb.clone(),
+            color => color.to_xyz().as_rgb_with(mapping),
+        }
+    }
+
+    pub fn to_lab(&self) -> ColorCieLab {
+        match self {
+            Color::CieLab(lab) => lab.clone(),
+            color => ColorCieLab::new_from_xyz(&color.to_xyz()),
+        }
+    }
+}
+
+/// Implements `From` for the variant of [`Color`] holding a color type
+macro_rules! impl_from_color {
+    ($($variant:ident($color:ty)),*) => {
+        $(
+            impl From<$color> for Color {
+                fn from(color: $color) -> Self {
+                    Color::$variant(color)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_color!(
+    Rgb(ColorRgb),
+    LinearRgb(ColorLinearRgb),
+    Xyz(ColorXyz),
+    CieLab(ColorCieLab),
+    CieLch(ColorCieLch),
+    CieLuv(ColorCieLuv),
+    OkLab(ColorOkLab),
+    OkLch(ColorOkLch),
+    Hsv(ColorHsv),
+    Hsl(ColorHsl),
+    YCbCr(ColorYCbCr)
+);
+
+#[cfg(test)]
+mod test {
+    use crate::colors::{
+        Color, ColorCieLab, ColorCieLch, ColorCieLuv, ColorHsl, ColorHsv, ColorLinearRgb,
+        ColorOkLab, ColorOkLch, ColorRgb, ColorXyz, ColorYCbCr, YCbCrStandard,
+    };
+
+    #[test]
+    fn every_variant_should_convert_back_to_rgb() {
+        // Arrange
+        let rgb = ColorRgb::new(210, 90, 30);
+        let xyz = ColorXyz::new_from_rgb(&rgb);
+        let colors: Vec<Color> = vec![
+            rgb.clone().into(),
+            ColorLinearRgb::new_from_xyz(&xyz).into(),
+            xyz.clone().into(),
+            ColorCieLab::new_from_xyz(&xyz).into(),
+            ColorCieLch::new_from_xyz(&xyz).into(),
+            ColorCieLuv::new_from_xyz(&xyz).into(),
+            ColorOkLab::new_from_xyz(&xyz).into(),
+            ColorOkLch::new_from_xyz(&xyz).into(),
+            ColorHsv::new_from_xyz(&xyz).into(),
+            ColorHsl::new_from_xyz(&xyz).into(),
+            ColorYCbCr::new_from_xyz(&xyz, YCbCrStandard::Bt709).into(),
+        ];
+
+        for color in colors.iter() {
+            // Act
+            let converted = color.to_rgb();
+            let lab = color.to_lab();
+
+            // Assert
+            assert_eq!(rgb, converted, "{:?}", color);
+            assert_eq!(rgb, lab.as_xyz().as_rgb(), "{:?}", color);
+        }
+    }
 }
@@ -0,0 +1,129 @@
+use super::{ColorLinearRgb, ColorXyz};
+use cgmath::Vector3;
+
+/// Linear sRGB to LMS cone responses, after Ottosson (2020)
+const RGB_TO_LMS: [[f64; 3]; 3] = [
+    [0.4122214708, 0.5363325363, 0.0514459929],
+    [0.2119034982, 0.6806995451, 0.1073969566],
+    [0.0883024619, 0.2817188376, 0.6299787005],
+];
+/// Cube rooted LMS to OKLab
+const LMS_TO_LAB: [[f64; 3]; 3] = [
+    [0.2104542553, 0.7936177850, -0.0040720468],
+    [1.9779984951, -2.4285922050, 0.4505937099],
+    [0.0259040371, 0.7827717662, -0.8086757660],
+];
+/// Inverse of [`LMS_TO_LAB`]
+const LAB_TO_LMS: [[f64; 3]; 3] = [
+    [1.0, 0.3963377774, 0.2158037573],
+    [1.0, -0.1055613458, -0.0638541728],
+    [1.0, -0.0894841775, -1.2914855480],
+];
+/// Inverse of [`RGB_TO_LMS`]
+const LMS_TO_RGB: [[f64; 3]; 3] = [
+    [4.0767416621, -3.3077115913, 0.2309699292],
+    [-1.2684380046, 2.6097574011, -0.3413193965],
+    [-0.0041960863, -0.7034186147, 1.7076147010],
+];
+
+fn multiply(matrix: &[[f64; 3]; 3], values: [f64; 3]) -> [f64; 3] {
+    let row = |r: &[f64; 3]| r[0] * values[0] + r[1] * values[1] + r[2] * values[2];
+
+    [row(&matrix[0]), row(&matrix[1]), row(&matrix[2])]
+}
+
+/// OKLab, a perceptual space where L runs from 0 to 1 and uniform steps look uniform
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorOkLab {
+    values: Vector3<f32>,
+}
+
+impl ColorOkLab {
+    pub fn l(&self) -> f32 {
+        self.values[0]
+    }
+    pub fn a(&self) -> f32 {
+        self.values[1]
+    }
+    pub fn b(&self) -> f32 {
+        self.values[2]
+    }
+    pub fn values(&self) -> &Vector3<f32> {
+        &self.values
+    }
+
+    pub const fn new(l: f32, a: f32, b: f32) -> ColorOkLab {
+        ColorOkLab {
+            values: Vector3::new(l, a, b),
+        }
+    }
+
+    pub fn new_from_linear_rgb(rgb: &ColorLinearRgb) -> ColorOkLab {
+        let lms = multiply(
+            &RGB_TO_LMS,
+            [rgb.r() as f64, rgb.g() as f64, rgb.b() as f64],
+        );
+        let [l, a, b] = multiply(&LMS_TO_LAB, [lms[0].cbrt(), lms[1].cbrt(), lms[2].cbrt()]);
+
+        ColorOkLab::new(l as f32, a as f32, b as f32)
+    }
+
+    pub fn as_linear_rgb(&self) -> ColorLinearRgb {
+        let lms = multiply(
+            &LAB_TO_LMS,
+            [self.l() as f64, self.a() as f64, self.b() as f64],
+        );
+        let [r, g, b] = multiply(
+            &LMS_TO_RGB,
+            [lms[0].powi(3), lms[1].powi(3), lms[2].powi(3)],
+        );
+
+        ColorLinearRgb::new(r as f32, g as f32, b as f32)
+    }
+
+    pub fn new_from_xyz(xyz: &ColorXyz) -> ColorOkLab {
+        Self::new_from_linear_rgb(&xyz.as_linear_rgb())
+    }
+
+    pub fn as_xyz(&self) -> ColorXyz {
+        self.as_linear_rgb().as_xyz()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::{ColorOkLab, ColorRgb, ColorXyz};
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn red_should_match_reference_values() {
+        // Arrange
+        let red = ColorXyz::new_from_rgb(&ColorRgb::new(255, 0, 0));
+
+        // Act
+        let oklab = ColorOkLab::new_from_xyz(&red);
+
+        // Assert
+        assert!((oklab.l() - 0.627955).abs() < 1e-4);
+        assert!((oklab.a() - 0.224863).abs() < 1e-4);
+        assert!((oklab.b() - 0.125846).abs() < 1e-4);
+    }
+
+    #[test]
+    fn convert_from_rgb_to_oklab_and_back_should_be_identity() {
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+
+        for _i in 0..100 {
+            // Arrange
+            let rgb = ColorRgb::new(rng.gen(), rng.gen(), rng.gen());
+
+            // Act
+            let oklab = ColorOkLab::new_from_xyz(&ColorXyz::new_from_rgb(&rgb));
+            let converted = oklab.as_xyz().as_rgb();
+
+            // Assert
+            assert_eq!(rgb, converted);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use super::lch::{from_polar, to_polar};
+use super::{ColorOkLab, ColorXyz};
+use cgmath::Vector3;
+
+/// OKLCh, the polar form of OKLab
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorOkLch {
+    values: Vector3<f32>,
+}
+
+impl ColorOkLch {
+    pub fn l(&self) -> f32 {
+        self.values[0]
+    }
+    pub fn c(&self) -> f32 {
+        self.values[1]
+    }
+    /// hue angle in degrees
+    pub fn h(&self) -> f32 {
+        self.values[2]
+    }
+    pub fn values(&self) -> &Vector3<f32> {
+        &self.values
+    }
+
+    pub const fn new(l: f32, c: f32, h: f32) -> ColorOkLch {
+        ColorOkLch {
+            values: Vector3::new(l, c, h),
+        }
+    }
+
+    pub fn new_from_oklab(lab: &ColorOkLab) -> ColorOkLch {
+        let (c, h) = to_polar(lab.a(), lab.b());
+        ColorOkLch::new(lab.l(), c, h)
+    }
+
+    pub fn as_oklab(&self) -> ColorOkLab {
+        let (a, b) = from_polar(self.c(), self.h());
+        ColorOkLab::new(self.l(), a, b)
+    }
+
+    pub fn new_from_xyz(xyz: &ColorXyz) -> ColorOkLch {
+        Self::new_from_oklab(&ColorOkLab::new_from_xyz(xyz))
+    }
+
+    pub fn as_xyz(&self) -> ColorXyz {
+        self.as_oklab().as_xyz()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::{ColorOkLch, ColorRgb, ColorXyz};
+
+    #[test]
+    fn blue_should_match_reference_values() {
+        // Arrange
+        let blue = ColorXyz::new_from_rgb(&ColorRgb::new(0, 0, 255));
+
+        // Act
+        let oklch = ColorOkLch::new_from_xyz(&blue);
+
+        // Assert
+        assert!((oklch.l() - 0.452014).abs() < 1e-4);
+        assert!((oklch.c() - 0.313214).abs() < 1e-4);
+        assert!((oklch.h() - 264.052).abs() < 1e-2);
+        assert_eq!(ColorRgb::new(0, 0, 255), oklch.as_xyz().as_rgb());
+    }
+}
@@ -1,7 +1,7 @@
 use cgmath::Vector3;
 use image::Rgb;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ColorRgb {
     values: Vector3<u8>,
 }
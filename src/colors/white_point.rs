@@ -0,0 +1,35 @@
+use super::ColorXyz;
+
+/// Reference white of a color space, CIE 1931 2° observer
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum WhitePoint {
+    /// horizon light, used by ICC profiles and print
+    D50,
+    /// mid-morning daylight
+    D55,
+    /// noon daylight, the white of sRGB
+    #[default]
+    D65,
+    /// north sky daylight
+    D75,
+    /// incandescent light
+    A,
+    /// any other white, with Y usually scaled to 100
+    Custom { x: f32, y: f32, z: f32 },
+}
+
+impl WhitePoint {
+    /// Tristimulus values of the white, scaled so Y is 100
+    pub fn xyz(&self) -> ColorXyz {
+        let (x, y, z) = match *self {
+            WhitePoint::D50 => (96.422, 100.0, 82.521),
+            WhitePoint::D55 => (95.682, 100.0, 92.149),
+            WhitePoint::D65 => (95.047, 100.0, 108.883),
+            WhitePoint::D75 => (94.972, 100.0, 122.638),
+            WhitePoint::A => (109.850, 100.0, 35.585),
+            WhitePoint::Custom { x, y, z } => (x, y, z),
+        };
+
+        ColorXyz { x, y, z }
+    }
+}
@@ -1,36 +1,51 @@
-use super::ColorRgb;
+use super::{ColorLinearRgb, ColorRgb, GamutMapping};
+
+/// Linear sRGB to XYZ under D65, after Lindbloom
+const RGB_TO_XYZ: [[f64; 3]; 3] = [
+    [0.4124564, 0.3575761, 0.1804375],
+    [0.2126729, 0.7151522, 0.0721750],
+    [0.0193339, 0.1191920, 0.9503041],
+];
+/// Inverse of [`RGB_TO_XYZ`]
+const XYZ_TO_RGB: [[f64; 3]; 3] = [
+    [3.2404542, -1.5371385, -0.4985314],
+    [-0.9692660, 1.8760108, 0.0415560],
+    [0.0556434, -0.2040259, 1.0572252],
+];
+
+fn multiply(matrix: &[[f64; 3]; 3], values: [f32; 3]) -> [f32; 3] {
+    let row = |r: &[f64; 3]| {
+        (r[0] * values[0] as f64 + r[1] * values[1] as f64 + r[2] * values[2] as f64) as f32
+    };
+
+    [row(&matrix[0]), row(&matrix[1]), row(&matrix[2])]
+}
 
 fn normalize_rgb(rgb: u8) -> f32 {
     rgb as f32 / 255.0
 }
 
-fn convert_to_pre_xyz(value: f32) -> f32 {
+/// Decodes the sRGB transfer function, giving linear light
+fn convert_to_linear(value: f32) -> f32 {
     const TRESHHOLD: f32 = 0.04045;
 
-    let result: f32;
-
     match value > TRESHHOLD {
-        true => result = ((value + 0.055) / 1.055).powf(2.4),
-        false => result = value / 12.92,
-    };
-
-    result * 100.0
+        true => ((value + 0.055) / 1.055).powf(2.4),
+        false => value / 12.92,
+    }
 }
 
-fn convert_to_pre_rgb(value: f32) -> f32 {
+/// Encodes linear light with the sRGB transfer function
+fn convert_to_srgb(value: f32) -> f32 {
     const TRESHHOLD: f32 = 0.0031308;
 
-    let result: f32;
-
     match value > TRESHHOLD {
-        true => result = 1.055 * value.powf(1.0 / 2.4) - 0.055,
-        false => result = 12.92 * value,
+        true => 1.055 * value.powf(1.0 / 2.4) - 0.055,
+        false => 12.92 * value,
     }
-
-    (result * 255.0).round()
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ColorXyz {
     pub x: f32,
     pub y: f32,
@@ -39,45 +54,85 @@ pub struct ColorXyz {
 
 impl ColorXyz {
     pub fn new_from_rgb(rgb: &ColorRgb) -> ColorXyz {
-        let r = convert_to_pre_xyz(normalize_rgb(rgb.r()));
-        let g = convert_to_pre_xyz(normalize_rgb(rgb.g()));
-        let b = convert_to_pre_xyz(normalize_rgb(rgb.b()));
-
-        ColorXyz {
-            x: r * 0.4124 + g * 0.3576 + b * 0.1805,
-            y: r * 0.2126 + g * 0.7152 + b * 0.0722,
-            z: r * 0.0193 + g * 0.1192 + b * 0.9505,
-        }
+        Self::new_from_srgb_f32(
+            normalize_rgb(rgb.r()),
+            normalize_rgb(rgb.g()),
+            normalize_rgb(rgb.b()),
+        )
     }
 
-    pub fn as_rgb(&self) -> ColorRgb {
-        let x = self.x / 100.0;
-        let y = self.y / 100.0;
-        let z = self.z / 100.0;
-
-        let r = x * 3.2406 + y * -1.5372 + z * -0.4986;
-        let g = x * -0.9689 + y * 1.8758 + z * 0.0415;
-        let b = x * 0.0557 + y * -0.2040 + z * 1.0570;
-
-        ColorRgb::new(
-            convert_to_pre_rgb(r) as u8,
-            convert_to_pre_rgb(g) as u8,
-            convert_to_pre_rgb(b) as u8,
+    /// Converts sRGB encoded components between 0 and 1, as decoded from 16 bit images
+    pub fn new_from_srgb_f32(r: f32, g: f32, b: f32) -> ColorXyz {
+        Self::new_from_linear_rgb(
+            convert_to_linear(r),
+            convert_to_linear(g),
+            convert_to_linear(b),
+        )
+    }
+
+    /// Converts linear-light RGB components with sRGB primaries, where 1 is the
+    /// white point. Float images hold linear light, brighter values are kept.
+    pub fn new_from_linear_rgb(r: f32, g: f32, b: f32) -> ColorXyz {
+        let [x, y, z] = multiply(&RGB_TO_XYZ, [r * 100.0, g * 100.0, b * 100.0]);
+
+        ColorXyz { x, y, z }
+    }
+
+    /// Linear-light RGB components with sRGB primaries, where 1 is the white point
+    pub fn as_linear_rgb(&self) -> ColorLinearRgb {
+        let [r, g, b] = multiply(
+            &XYZ_TO_RGB,
+            [self.x / 100.0, self.y / 100.0, self.z / 100.0],
+        );
+
+        ColorLinearRgb::new(r, g, b)
+    }
+
+    /// sRGB encoded components, between 0 and 1 for colors inside the sRGB gamut
+    pub fn as_srgb_f32(&self) -> (f32, f32, f32) {
+        let linear = self.as_linear_rgb();
+
+        (
+            convert_to_srgb(linear.r()),
+            convert_to_srgb(linear.g()),
+            convert_to_srgb(linear.b()),
         )
     }
+
+    /// True if the linear sRGB components lie between 0 and 1, give or take `tolerance`
+    pub fn is_in_gamut(&self, tolerance: f32) -> bool {
+        let linear = self.as_linear_rgb();
+
+        [linear.r(), linear.g(), linear.b()]
+            .iter()
+            .all(|v| *v >= -tolerance && *v <= 1.0 + tolerance)
+    }
+
+    /// 8 bit sRGB color, brought into the gamut by the default [`GamutMapping`]
+    pub fn as_rgb(&self) -> ColorRgb {
+        self.as_rgb_with(GamutMapping::default())
+    }
+
+    pub fn as_rgb_with(&self, mapping: GamutMapping) -> ColorRgb {
+        let (r, g, b) = mapping.map(self);
+        let quantize = |v: f32| (v * 255.0).round() as u8;
+
+        ColorRgb::new(quantize(r), quantize(g), quantize(b))
+    }
 }
 
 #[cfg(test)]
 mod test {
 
     use crate::colors::{ColorRgb, ColorXyz};
-    use rand::Rng;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
 
     #[test]
     fn converting_from_rgb_to_xyz_and_back_should_be_identity() {
-        let mut rng = rand::thread_rng();
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
 
-        for i in 0..100 {
+        for _i in 0..100 {
             let r = rng.gen_range(0, 256) as u8;
             let g = rng.gen_range(0, 256) as u8;
             let b = rng.gen_range(0, 256) as u8;
@@ -93,4 +148,24 @@ mod test {
             assert_eq!(rgb, converted);
         }
     }
+
+    #[test]
+    fn primaries_should_match_reference_values() {
+        // Arrange
+        let cases = [
+            ((255, 0, 0), (41.2456, 21.2673, 1.9334)),
+            ((0, 255, 0), (35.7576, 71.5152, 11.9192)),
+            ((0, 0, 255), (18.0437, 7.2175, 95.0304)),
+            ((255, 255, 255), (95.047, 100.0, 108.883)),
+        ];
+
+        for ((r, g, b), (x, y, z)) in cases.iter() {
+            // Act
+            let actual = ColorXyz::new_from_rgb(&ColorRgb::new(*r, *g, *b));
+
+            // Assert
+            let delta = [actual.x - x, actual.y - y, actual.z - z];
+            assert!(delta.iter().all(|d| d.abs() < 1e-3), "{:?}", actual);
+        }
+    }
 }
@@ -0,0 +1,126 @@
+use super::ColorXyz;
+use cgmath::Vector3;
+
+/// Luma weights of the red and blue primaries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum YCbCrStandard {
+    /// ITU-R BT.601, standard definition video and JPEG
+    #[default]
+    Bt601,
+    /// ITU-R BT.709, high definition video
+    Bt709,
+}
+
+impl YCbCrStandard {
+    /// Weights (Kr, Kb) of red and blue in luma
+    fn weights(&self) -> (f32, f32) {
+        match self {
+            YCbCrStandard::Bt601 => (0.299, 0.114),
+            YCbCrStandard::Bt709 => (0.2126, 0.0722),
+        }
+    }
+}
+
+/// Full range luma and chroma differences of sRGB encoded components,
+/// Y is between 0 and 1, Cb and Cr between -0.5 and 0.5
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorYCbCr {
+    values: Vector3<f32>,
+    standard: YCbCrStandard,
+}
+
+impl ColorYCbCr {
+    pub fn y(&self) -> f32 {
+        self.values[0]
+    }
+    pub fn cb(&self) -> f32 {
+        self.values[1]
+    }
+    pub fn cr(&self) -> f32 {
+        self.values[2]
+    }
+    pub fn values(&self) -> &Vector3<f32> {
+        &self.values
+    }
+    pub fn standard(&self) -> YCbCrStandard {
+        self.standard
+    }
+
+    pub const fn new(y: f32, cb: f32, cr: f32, standard: YCbCrStandard) -> ColorYCbCr {
+        ColorYCbCr {
+            values: Vector3::new(y, cb, cr),
+            standard,
+        }
+    }
+
+    pub fn new_from_srgb_f32(r: f32, g: f32, b: f32, standard: YCbCrStandard) -> ColorYCbCr {
+        let (kr, kb) = standard.weights();
+        let y = kr * r + (1.0 - kr - kb) * g + kb * b;
+
+        ColorYCbCr::new(
+            y,
+            (b - y) / (2.0 * (1.0 - kb)),
+            (r - y) / (2.0 * (1.0 - kr)),
+            standard,
+        )
+    }
+
+    /// sRGB encoded components between 0 and 1
+    pub fn as_srgb_f32(&self) -> (f32, f32, f32) {
+        let (kr, kb) = self.standard.weights();
+        let r = self.y() + 2.0 * (1.0 - kr) * self.cr();
+        let b = self.y() + 2.0 * (1.0 - kb) * self.cb();
+        let g = (self.y() - kr * r - kb * b) / (1.0 - kr - kb);
+
+        (r, g, b)
+    }
+
+    pub fn new_from_xyz(xyz: &ColorXyz, standard: YCbCrStandard) -> ColorYCbCr {
+        let (r, g, b) = xyz.as_srgb_f32();
+        Self::new_from_srgb_f32(r, g, b, standard)
+    }
+
+    pub fn as_xyz(&self) -> ColorXyz {
+        let (r, g, b) = self.as_srgb_f32();
+        ColorXyz::new_from_srgb_f32(r, g, b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::{ColorRgb, ColorXyz, ColorYCbCr, YCbCrStandard};
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn primaries_should_match_reference_values() {
+        // Act
+        let red = ColorYCbCr::new_from_srgb_f32(1.0, 0.0, 0.0, YCbCrStandard::Bt601);
+        let blue = ColorYCbCr::new_from_srgb_f32(0.0, 0.0, 1.0, YCbCrStandard::Bt709);
+
+        // Assert
+        assert!((red.y() - 0.299).abs() < 1e-6);
+        assert!((red.cr() - 0.5).abs() < 1e-6);
+        assert!((blue.y() - 0.0722).abs() < 1e-6);
+        assert!((blue.cb() - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn convert_from_rgb_to_ycbcr_and_back_should_be_identity() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+
+        for standard in [YCbCrStandard::Bt601, YCbCrStandard::Bt709] {
+            for _i in 0..100 {
+                // Arrange
+                let rgb = ColorRgb::new(rng.gen(), rng.gen(), rng.gen());
+
+                // Act
+                let ycbcr = ColorYCbCr::new_from_xyz(&ColorXyz::new_from_rgb(&rgb), standard);
+                let converted = ycbcr.as_xyz().as_rgb();
+
+                // Assert
+                assert_eq!(rgb, converted);
+            }
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::VecDeque;
+
+/// 4-connected components of a row-major label map
+struct Components {
+    /// component of every pixel
+    ids: Vec<usize>,
+    /// pixels of every component
+    members: Vec<Vec<usize>>,
+}
+
+impl Components {
+    fn new(labels: &[usize], width: usize, height: usize) -> Components {
+        let mut ids = vec![usize::MAX; labels.len()];
+        let mut members: Vec<Vec<usize>> = Vec::new();
+        let mut queue = VecDeque::new();
+
+        for start in 0..labels.len() {
+            if ids[start] != usize::MAX {
+                continue;
+            }
+
+            let id = members.len();
+            let mut pixels = Vec::new();
+            ids[start] = id;
+            queue.push_back(start);
+
+            while let Some(index) = queue.pop_front() {
+                pixels.push(index);
+
+                for neighbor in neighbors(index, width, height) {
+                    if ids[neighbor] == usize::MAX && labels[neighbor] == labels[index] {
+                        ids[neighbor] = id;
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+
+            members.push(pixels);
+        }
+
+        Components { ids, members }
+    }
+}
+
+/// Indices of the 4-connected neighbors of a pixel
+fn neighbors(index: usize, width: usize, height: usize) -> impl Iterator<Item = usize> {
+    let (x, y) = (index % width, index / width);
+
+    let left = if x > 0 { Some(index - 1) } else { None };
+    let right = if x + 1 < width { Some(index + 1) } else { None };
+    let up = if y > 0 { Some(index - width) } else { None };
+    let down = if y + 1 < height {
+        Some(index + width)
+    } else {
+        None
+    };
+
+    left.into_iter().chain(right).chain(up).chain(down)
+}
+
+fn find(parents: &mut [usize], id: usize) -> usize {
+    let mut root = id;
+    while parents[root] != root {
+        root = parents[root];
+    }
+
+    // compress the path, so later lookups are cheap
+    let mut current = id;
+    while parents[current] != root {
+        let next = parents[current];
+        parents[current] = root;
+        current = next;
+    }
+
+    root
+}
+
+/// Relabels a row-major label map so every label is a single 4-connected region.
+/// Regions smaller than `min_size` pixels are merged into their largest adjacent
+/// region. Returns the new labels, numbered in order of first appearance, and
+/// the number of distinct labels. Pixels labeled `usize::MAX` are left out, they
+/// are neither merged nor merged into and keep their label.
+pub fn enforce_connectivity(
+    labels: &[usize],
+    width: usize,
+    height: usize,
+    min_size: usize,
+) -> (Vec<usize>, usize) {
+    let components = Components::new(labels, width, height);
+    let count = components.members.len();
+    let unlabeled: Vec<bool> = components
+        .members
+        .iter()
+        .map(|m| labels[m[0]] == usize::MAX)
+        .collect();
+
+    let mut parents: Vec<usize> = (0..count).collect();
+    let mut sizes: Vec<usize> = components.members.iter().map(|m| m.len()).collect();
+    // components merged into a root, so neighbors can be found for the whole group
+    let mut groups: Vec<Vec<usize>> = (0..count).map(|id| vec![id]).collect();
+
+    // smallest fragments go first, so they can grow into their neighbors
+    let mut order: Vec<usize> = (0..count).collect();
+    order.sort_by_key(|id| sizes[*id]);
+
+    for id in order {
+        let root = find(&mut parents, id);
+        if unlabeled[root] || sizes[root] >= min_size {
+            continue;
+        }
+
+        let mut largest: Option<usize> = None;
+        for member in &groups[root] {
+            for pixel in &components.members[*member] {
+                for neighbor in neighbors(*pixel, width, height) {
+                    let neighbor_root = find(&mut parents, components.ids[neighbor]);
+                    if neighbor_root == root || unlabeled[neighbor_root] {
+                        continue;
+                    }
+
+                    largest = match largest {
+                        Some(current)
+                            if sizes[current] > sizes[neighbor_root]
+                                || (sizes[current] == sizes[neighbor_root]
+                                    && current < neighbor_root) =>
+                        {
+                            Some(current)
+                        }
+                        _ => Some(neighbor_root),
+                    };
+                }
+            }
+        }
+
+        // a region without labeled neighbors covers all labeled pixels and is kept as is
+        if let Some(target) = largest {
+            parents[root] = target;
+            sizes[target] += sizes[root];
+            let merged = std::mem::take(&mut groups[root]);
+            groups[target].extend(merged);
+        }
+    }
+
+    let mut new_ids = vec![usize::MAX; count];
+    let mut new_count = 0;
+    let new_labels = components
+        .ids
+        .iter()
+        .map(|id| {
+            let root = find(&mut parents, *id);
+            if unlabeled[root] {
+                return usize::MAX;
+            }
+            if new_ids[root] == usize::MAX {
+                new_ids[root] = new_count;
+                new_count += 1;
+            }
+            new_ids[root]
+        })
+        .collect();
+
+    (new_labels, new_count)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::connectivity::enforce_connectivity;
+
+    #[test]
+    fn disconnected_labels_should_be_split() {
+        // Arrange
+        #[rustfmt::skip]
+        let labels = vec![
+            0, 0, 1, 0, 0,
+            0, 0, 1, 0, 0,
+            0, 0, 1, 0, 0,
+        ];
+
+        // Act
+        let (actual, count) = enforce_connectivity(&labels, 5, 3, 0);
+
+        // Assert
+        #[rustfmt::skip]
+        let expected = vec![
+            0, 0, 1, 2, 2,
+            0, 0, 1, 2, 2,
+            0, 0, 1, 2, 2,
+        ];
+        assert_eq!(3, count);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn small_islands_should_merge_into_largest_neighbor() {
+        // Arrange
+        #[rustfmt::skip]
+        let labels = vec![
+            0, 0, 0, 1, 1,
+            0, 2, 0, 1, 1,
+            0, 0, 0, 1, 1,
+            0, 0, 0, 1, 1,
+        ];
+
+        // Act
+        let (actual, count) = enforce_connectivity(&labels, 5, 4, 2);
+
+        // Assert
+        #[rustfmt::skip]
+        let expected = vec![
+            0, 0, 0, 1, 1,
+            0, 0, 0, 1, 1,
+            0, 0, 0, 1, 1,
+            0, 0, 0, 1, 1,
+        ];
+        assert_eq!(2, count);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn single_region_should_be_kept() {
+        // Arrange
+        let labels = vec![3; 6];
+
+        // Act
+        let (actual, count) = enforce_connectivity(&labels, 3, 2, 100);
+
+        // Assert
+        assert_eq!(1, count);
+        assert_eq!(vec![0; 6], actual);
+    }
+
+    #[test]
+    fn unlabeled_pixels_should_be_left_out() {
+        // Arrange
+        const X: usize = usize::MAX;
+        #[rustfmt::skip]
+        let labels = vec![
+            X, 0, 0, 0,
+            X, 0, 1, 0,
+            X, X, 0, 0,
+        ];
+
+        // Act
+        let (actual, count) = enforce_connectivity(&labels, 4, 3, 2);
+
+        // Assert
+        #[rustfmt::skip]
+        let expected = vec![
+            X, 0, 0, 0,
+            X, 0, 0, 0,
+            X, X, 0, 0,
+        ];
+        assert_eq!(1, count);
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,39 @@
+use image::{ColorType, ImageError};
+use std::fmt;
+
+/// Everything that can go wrong while clustering an image
+#[derive(Debug)]
+pub enum Error {
+    /// the image could not be read or decoded
+    Decode(ImageError),
+    /// the image uses a pixel format that can't be clustered
+    UnsupportedPixelFormat(ColorType),
+    /// the parameters don't fit the input, e.g. more superpixels than pixels
+    InvalidParameters(String),
+    /// the result could not be encoded or written
+    Encode(ImageError),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Decode(e) => write!(f, "failed to decode image: {}", e),
+            Error::UnsupportedPixelFormat(color) => {
+                write!(f, "unsupported pixel format: {:?}", color)
+            }
+            Error::InvalidParameters(reason) => write!(f, "invalid parameters: {}", reason),
+            Error::Encode(e) => write!(f, "failed to encode image: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Decode(e) | Error::Encode(e) => Some(e),
+            _ => None,
+        }
+    }
+}
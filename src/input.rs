@@ -0,0 +1,130 @@
+use crate::colors::{ClusterColor, ColorRgb, ColorXyz};
+use crate::error::{Error, Result};
+use crate::pixels::{Pixel, PixelCieLab};
+use image::{DynamicImage, ImageBuffer, Pixel as ImagePixel};
+
+fn convert<P: ImagePixel, C: ClusterColor>(
+    image: &ImageBuffer<P, Vec<P::Subpixel>>,
+    to_xyz: impl Fn(&[P::Subpixel]) -> ColorXyz,
+) -> Vec<Pixel<C>> {
+    image
+        .enumerate_pixels()
+        .map(|(x, y, p)| {
+            let xyz = to_xyz(p.channels());
+            Pixel::new(x as f32, y as f32, C::from_xyz(&xyz))
+        })
+        .collect()
+}
+
+fn from_srgb8(r: u8, g: u8, b: u8) -> ColorXyz {
+    ColorXyz::new_from_rgb(&ColorRgb::new(r, g, b))
+}
+
+fn from_srgb16(r: u16, g: u16, b: u16) -> ColorXyz {
+    let normalize = |v: u16| v as f32 / u16::MAX as f32;
+    ColorXyz::new_from_srgb_f32(normalize(r), normalize(g), normalize(b))
+}
+
+/// Converts an image to CIE Lab pixels, in row-major order. 8 and 16 bit images are
+/// decoded as sRGB, float images hold linear light. Alpha is ignored, see [`crate::Mask::from_opacity`].
+pub fn pixels_from_image(image: &DynamicImage) -> Result<Vec<PixelCieLab>> {
+    pixels_from_image_as(image)
+}
+
+/// Converts an image to pixels of the color space `C`, like [`pixels_from_image`]
+pub fn pixels_from_image_as<C: ClusterColor>(image: &DynamicImage) -> Result<Vec<Pixel<C>>> {
+    let pixels = match image {
+        DynamicImage::ImageLuma8(i) => convert(i, |c| from_srgb8(c[0], c[0], c[0])),
+        DynamicImage::ImageLumaA8(i) => convert(i, |c| from_srgb8(c[0], c[0], c[0])),
+        DynamicImage::ImageRgb8(i) => convert(i, |c| from_srgb8(c[0], c[1], c[2])),
+        DynamicImage::ImageRgba8(i) => convert(i, |c| from_srgb8(c[0], c[1], c[2])),
+        DynamicImage::ImageLuma16(i) => convert(i, |c| from_srgb16(c[0], c[0], c[0])),
+        DynamicImage::ImageLumaA16(i) => convert(i, |c| from_srgb16(c[0], c[0], c[0])),
+        DynamicImage::ImageRgb16(i) => convert(i, |c| from_srgb16(c[0], c[1], c[2])),
+        DynamicImage::ImageRgba16(i) => convert(i, |c| from_srgb16(c[0], c[1], c[2])),
+        DynamicImage::ImageRgb32F(i) => {
+            convert(i, |c| ColorXyz::new_from_linear_rgb(c[0], c[1], c[2]))
+        }
+        DynamicImage::ImageRgba32F(i) => {
+            convert(i, |c| ColorXyz::new_from_linear_rgb(c[0], c[1], c[2]))
+        }
+        _ => return Err(Error::UnsupportedPixelFormat(image.color())),
+    };
+
+    Ok(pixels)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::input::pixels_from_image;
+    use image::{
+        DynamicImage, GrayImage, ImageBuffer, Luma, LumaA, Rgb, Rgb32FImage, RgbImage, Rgba,
+        RgbaImage,
+    };
+
+    fn lab(image: DynamicImage) -> [f32; 3] {
+        let pixels = pixels_from_image(&image).unwrap();
+        let color = pixels[0].color();
+        [color.l(), color.a(), color.b()]
+    }
+
+    fn assert_close(expected: [f32; 3], actual: [f32; 3]) {
+        for (e, a) in expected.iter().zip(actual.iter()) {
+            assert!((e - a).abs() < 1e-3, "{:?} != {:?}", expected, actual);
+        }
+    }
+
+    #[test]
+    fn every_format_should_convert_to_the_same_color() {
+        // Arrange
+        let expected = lab(DynamicImage::ImageRgb8(RgbImage::from_pixel(
+            1,
+            1,
+            Rgb([128, 128, 128]),
+        )));
+        let gray = 128_u16 * 257;
+        let linear = ((128.0_f32 / 255.0 + 0.055) / 1.055).powf(2.4);
+
+        let images = vec![
+            DynamicImage::ImageLuma8(GrayImage::from_pixel(1, 1, Luma([128]))),
+            DynamicImage::ImageLumaA8(ImageBuffer::from_pixel(1, 1, LumaA([128, 7]))),
+            DynamicImage::ImageRgba8(RgbaImage::from_pixel(1, 1, Rgba([128, 128, 128, 0]))),
+            DynamicImage::ImageLuma16(ImageBuffer::from_pixel(1, 1, Luma([gray]))),
+            DynamicImage::ImageRgb16(ImageBuffer::from_pixel(1, 1, Rgb([gray, gray, gray]))),
+            DynamicImage::ImageRgb32F(Rgb32FImage::from_pixel(1, 1, Rgb([linear; 3]))),
+        ];
+
+        for image in images {
+            // Act
+            let actual = lab(image);
+
+            // Assert
+            assert_close(expected, actual);
+        }
+    }
+
+    #[test]
+    fn sixteen_bit_images_should_keep_their_precision() {
+        // Arrange
+        let dark = |v: u16| DynamicImage::ImageLuma16(ImageBuffer::from_pixel(1, 1, Luma([v])));
+
+        // Act
+        let (a, b) = (lab(dark(1000)), lab(dark(1001)));
+
+        // Assert
+        assert!(a[0] < b[0]);
+    }
+
+    #[test]
+    fn linear_white_should_be_full_lightness() {
+        // Act
+        let actual = lab(DynamicImage::ImageRgb32F(Rgb32FImage::from_pixel(
+            1,
+            1,
+            Rgb([1.0; 3]),
+        )));
+
+        // Assert
+        assert!((actual[0] - 100.0).abs() < 0.1);
+    }
+}
@@ -0,0 +1,453 @@
+use super::{
+    CancellationToken, Compactness, KMeansSuperPixelSolver, ProgressEvent, ProgressObserver,
+    DEFAULT_MAX_ITERATIONS, DEFAULT_MIN_SIZE_FRACTION, DEFAULT_RESIDUAL_THRESHOLD,
+    DEFAULT_RNG_SEED, DEFAULT_SEED_NEIGHBORHOOD,
+};
+use crate::colors::{ClusterColor, ColorCieLab, ColorDifference};
+use crate::error::{Error, Result};
+use crate::mask::Mask;
+use crate::pixels::{DistanceModel, Pixel};
+use crate::seeding::SeedLayout;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How many superpixels to cluster into
+#[derive(Debug, Clone, Copy)]
+enum Target {
+    Count(usize),
+    /// edge length S of a superpixel, in pixels
+    Size(f32),
+}
+
+/// Configures and creates a [`KMeansSuperPixelSolver`], clustering in the color
+/// space of the pixels
+///
+/// ```
+/// use image_clustering::colors::ColorCieLab;
+/// use image_clustering::pixels::PixelCieLab;
+/// use image_clustering::{Compactness, SeedLayout, SolverBuilder};
+///
+/// let pixels = (0..30 * 20)
+///     .map(|i| {
+///         let (x, y) = ((i % 30) as f32, (i / 30) as f32);
+///         PixelCieLab::new(x, y, ColorCieLab::new(50.0, 0.0, 0.0))
+///     })
+///     .collect();
+///
+/// let mut solver = SolverBuilder::new(pixels, 30, 20)
+///     .superpixel_count(6)
+///     .compactness(Compactness::Adaptive)
+///     .seed_layout(SeedLayout::Hexagonal)
+///     .max_iterations(5)
+///     .build()
+///     .unwrap();
+/// solver.solve();
+/// ```
+pub struct SolverBuilder<C: ClusterColor = ColorCieLab> {
+    pixels: Vec<Pixel<C>>,
+    width: usize,
+    height: usize,
+    target: Target,
+    compactness: Compactness,
+    residual_threshold: f32,
+    max_iterations: usize,
+    seed_layout: SeedLayout,
+    seed_neighborhood: Option<u32>,
+    distance_model: DistanceModel,
+    color_difference: Option<ColorDifference>,
+    min_size_fraction: Option<f32>,
+    rng_seed: u64,
+    thread_count: Option<usize>,
+    observer: Option<Arc<dyn ProgressObserver>>,
+    cancellation: Option<CancellationToken>,
+    time_budget: Option<Duration>,
+    mask: Option<Mask>,
+}
+
+impl<C: ClusterColor> SolverBuilder<C> {
+    /// Starts a solver for the pixels of a `width` x `height` image, with the
+    /// settings recommended by the SLIC paper
+    pub fn new(pixels: Vec<Pixel<C>>, width: usize, height: usize) -> SolverBuilder<C> {
+        SolverBuilder {
+            pixels,
+            width,
+            height,
+            target: Target::Count(600),
+            compactness: Compactness::Fixed(10),
+            residual_threshold: DEFAULT_RESIDUAL_THRESHOLD,
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+            seed_layout: SeedLayout::default(),
+            seed_neighborhood: Some(DEFAULT_SEED_NEIGHBORHOOD),
+            distance_model: DistanceModel::default(),
+            color_difference: None,
+            min_size_fraction: Some(DEFAULT_MIN_SIZE_FRACTION),
+            rng_seed: DEFAULT_RNG_SEED,
+            thread_count: None,
+            observer: None,
+            cancellation: None,
+            time_budget: None,
+            mask: None,
+        }
+    }
+
+    /// Number of superpixels to cluster into
+    pub fn superpixel_count(mut self, count: usize) -> Self {
+        self.target = Target::Count(count);
+        self
+    }
+
+    /// Edge length of a superpixel in pixels, instead of a superpixel count
+    pub fn superpixel_size(mut self, size: f32) -> Self {
+        self.target = Target::Size(size);
+        self
+    }
+
+    pub fn compactness(mut self, compactness: impl Into<Compactness>) -> Self {
+        self.compactness = compactness.into();
+        self
+    }
+
+    /// Residual below which [`KMeansSuperPixelSolver::solve`] stops
+    pub fn residual_threshold(mut self, threshold: f32) -> Self {
+        self.residual_threshold = threshold;
+        self
+    }
+
+    /// Number of ticks after which [`KMeansSuperPixelSolver::solve`] stops
+    pub fn max_iterations(mut self, iterations: usize) -> Self {
+        self.max_iterations = iterations;
+        self
+    }
+
+    pub fn seed_layout(mut self, layout: SeedLayout) -> Self {
+        self.seed_layout = layout;
+        self
+    }
+
+    /// Size of the neighborhood seeds are moved to the lowest gradient in, which
+    /// needs to be odd. `None` keeps them where the layout placed them.
+    pub fn seed_neighborhood(mut self, neighborhood: Option<u32>) -> Self {
+        self.seed_neighborhood = neighborhood;
+        self
+    }
+
+    pub fn distance_model(mut self, model: DistanceModel) -> Self {
+        self.distance_model = model;
+        self
+    }
+
+    /// Compares colors by a perceptual color difference in CIE Lab, instead of the
+    /// euclidean distance in the color space of the pixels
+    pub fn color_difference(mut self, difference: ColorDifference) -> Self {
+        self.color_difference = Some(difference);
+        self
+    }
+
+    /// Fraction of a superpixel below which fragments are merged into their
+    /// neighbors after solving, `None` skips connectivity enforcement
+    pub fn connectivity(mut self, min_size_fraction: Option<f32>) -> Self {
+        self.min_size_fraction = min_size_fraction;
+        self
+    }
+
+    /// Seed of the random number generator used by random seed layouts. The same
+    /// pixels, options and seed always give the same labels, whatever the thread count.
+    pub fn rng_seed(mut self, seed: u64) -> Self {
+        self.rng_seed = seed;
+        self
+    }
+
+    /// Number of threads to solve on, the global rayon pool is used if not set
+    pub fn thread_count(mut self, count: usize) -> Self {
+        self.thread_count = Some(count);
+        self
+    }
+
+    /// Receives progress events of seeding, every tick and post-processing
+    pub fn observer(mut self, observer: impl ProgressObserver + 'static) -> Self {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Token that aborts solving once cancelled, keep a clone to cancel it
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Wall-clock time every [`KMeansSuperPixelSolver::solve_until`] run may take
+    pub fn time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    /// Only clusters pixels inside the mask. Seeds are placed inside the mask, the
+    /// superpixel count or size applies to its area and the other pixels are never
+    /// assigned to a superpixel.
+    pub fn mask(mut self, mask: Mask) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    fn invalid<T>(reason: String) -> Result<T> {
+        Err(Error::InvalidParameters(reason))
+    }
+
+    fn superpixel_count_for(&self, pixel_count: usize) -> Result<usize> {
+        let count = match self.target {
+            Target::Count(count) => count,
+            Target::Size(size) if size.is_finite() && size >= 1.0 => {
+                ((pixel_count as f64 / (size as f64).powi(2)).round() as usize).max(1)
+            }
+            Target::Size(size) => {
+                return Self::invalid(format!(
+                    "superpixel size needs to be at least 1, got {}",
+                    size
+                ))
+            }
+        };
+
+        if count == 0 {
+            return Self::invalid(String::from("at least one superpixel is needed"));
+        }
+        if count > pixel_count {
+            return Self::invalid(format!(
+                "{} superpixels don't fit into {} pixels",
+                count, pixel_count
+            ));
+        }
+
+        Ok(count)
+    }
+
+    /// Validates the configuration, places the seeds and assigns every pixel to its
+    /// closest seed
+    pub fn build(self) -> Result<KMeansSuperPixelSolver<C>> {
+        let pixel_count = match &self.mask {
+            Some(mask) => {
+                mask.check_size(self.width, self.height)?;
+                mask.count()
+            }
+            None => self.width * self.height,
+        };
+        if pixel_count == 0 {
+            return Self::invalid(String::from("the mask needs to contain at least one pixel"));
+        }
+        let superpixel_count = self.superpixel_count_for(pixel_count)?;
+
+        if self.residual_threshold.is_nan() {
+            return Self::invalid(String::from("residual threshold must be a number"));
+        }
+        if let Some(neighborhood) = self.seed_neighborhood {
+            KMeansSuperPixelSolver::<C>::check_seed_neighborhood(neighborhood)?;
+        }
+        if let Some(fraction) = self.min_size_fraction {
+            if !(fraction.is_finite() && fraction >= 0.0) {
+                return Self::invalid(format!(
+                    "minimum size fraction needs to be positive, got {}",
+                    fraction
+                ));
+            }
+        }
+
+        let thread_pool = match self.thread_count {
+            Some(0) => return Self::invalid(String::from("at least one thread is needed")),
+            Some(count) => Some(Arc::new(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(count)
+                    .build()
+                    .map_err(|e| Error::InvalidParameters(e.to_string()))?,
+            )),
+            None => None,
+        };
+
+        let mut rng = ChaCha8Rng::seed_from_u64(self.rng_seed);
+
+        let mut flat_pixels =
+            KMeansSuperPixelSolver::place_pixels(self.pixels, self.width, self.height)?;
+        if let Some(mask) = &self.mask {
+            for (pixel, inside) in flat_pixels.iter_mut().zip(mask.values()) {
+                pixel.excluded = !inside;
+            }
+        }
+
+        let mut solver = KMeansSuperPixelSolver {
+            flat_pixels,
+            centroids: Vec::new(),
+            height: self.height,
+            width: self.width,
+            compactness: self.compactness,
+            distance_model: self.distance_model,
+            color_difference: self.color_difference,
+            max_color_distances: Vec::new(),
+            s: KMeansSuperPixelSolver::<C>::calculate_superpixel_size(
+                pixel_count,
+                superpixel_count,
+            ),
+            k: superpixel_count,
+            requested_k: superpixel_count,
+            residual_threshold: self.residual_threshold,
+            max_iterations: self.max_iterations,
+            min_size_fraction: self.min_size_fraction,
+            thread_pool,
+            observer: self.observer,
+            cancellation: self.cancellation,
+            time_budget: self.time_budget,
+            deadline: None,
+        };
+
+        solver.update_pixel_labs();
+        solver.calculate_initial_centroids(self.seed_layout, &mut rng);
+
+        match self.seed_neighborhood {
+            Some(neighborhood) => solver.perturb_seeds(neighborhood)?,
+            None => {
+                solver.assign_pixels_to_superpixels();
+            }
+        }
+        solver.report(ProgressEvent::Seeded {
+            superpixel_count: solver.k,
+        });
+
+        Ok(solver)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::{ColorCieLab, ColorDifference};
+    use crate::error::Error;
+    use crate::k_means_solver::SolverBuilder;
+    use crate::mask::Mask;
+    use crate::pixels::PixelCieLab;
+    use crate::seeding::SeedLayout;
+    use crate::segmentation::NO_LABEL;
+
+    fn pixels(width: u32, height: u32) -> Vec<PixelCieLab> {
+        (0..width * height)
+            .map(|i| {
+                let (x, y) = (i % width, i / width);
+                PixelCieLab::new(
+                    x as f32,
+                    y as f32,
+                    ColorCieLab::new((x * 2) as f32, y as f32, 0.0),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn superpixel_size_should_derive_count() {
+        // Act
+        let solver = SolverBuilder::new(pixels(40, 30), 40, 30)
+            .superpixel_size(10.0)
+            .build()
+            .unwrap();
+
+        // Assert
+        assert_eq!(12, solver.requested_superpixel_count());
+    }
+
+    #[test]
+    fn options_should_be_applied() {
+        // Act
+        let mut solver = SolverBuilder::new(pixels(40, 30), 40, 30)
+            .superpixel_count(12)
+            .seed_layout(SeedLayout::Jittered)
+            .rng_seed(3)
+            .max_iterations(2)
+            .residual_threshold(-1.0)
+            .connectivity(None)
+            .thread_count(2)
+            .color_difference(ColorDifference::Ciede2000)
+            .build()
+            .unwrap();
+        let report = solver.solve();
+
+        // Assert
+        assert_eq!(2, report.iterations());
+        assert_eq!(12, solver.superpixel_count());
+        assert_eq!(Some(ColorDifference::Ciede2000), solver.color_difference());
+    }
+
+    #[test]
+    fn invalid_options_should_error_on_build() {
+        // Arrange
+        let builder = || SolverBuilder::new(pixels(4, 3), 4, 3).superpixel_count(2);
+        let builders = vec![
+            builder().superpixel_count(0),
+            builder().superpixel_count(13),
+            builder().superpixel_size(0.5),
+            builder().residual_threshold(f32::NAN),
+            builder().seed_neighborhood(Some(0)),
+            builder().seed_neighborhood(Some(4)),
+            builder().connectivity(Some(-1.0)),
+            builder().thread_count(0),
+            SolverBuilder::new(pixels(4, 3), 4, 4).superpixel_count(2),
+        ];
+
+        for builder in builders {
+            // Act
+            let result = builder.build();
+
+            // Assert
+            assert!(matches!(result, Err(Error::InvalidParameters(_))));
+        }
+    }
+
+    #[test]
+    fn masked_pixels_should_get_no_seeds_and_no_label() {
+        // Arrange
+        let (width, height) = (60, 40);
+        let mask = Mask::from_fn(width, height, |x, y| {
+            (x as i32 - 40).pow(2) + (y as i32 - 20).pow(2) < 15 * 15
+        });
+        let inside = mask.count();
+
+        // Act
+        let mut solver = SolverBuilder::new(pixels(width, height), 60, 40)
+            .superpixel_size(6.0)
+            .mask(mask.clone())
+            .build()
+            .unwrap();
+        solver.solve();
+        let segmentation = solver.segmentation();
+
+        // Assert
+        assert!(solver
+            .centroids()
+            .iter()
+            .all(|c| mask.contains(c.x().round() as u32, c.y().round() as u32)));
+        let expected = (inside as f32 / 36.0).round() as usize;
+        assert!((solver.requested_superpixel_count() as i64 - expected as i64).abs() <= 1);
+        for y in 0..height {
+            for x in 0..width {
+                assert_eq!(!mask.contains(x, y), segmentation.label(x, y) == NO_LABEL);
+            }
+        }
+    }
+
+    #[test]
+    fn masks_should_match_the_image() {
+        // Act
+        let wrong_size = SolverBuilder::new(pixels(4, 3), 4, 3)
+            .superpixel_count(2)
+            .mask(Mask::from_fn(3, 4, |_, _| true))
+            .build();
+        let empty = SolverBuilder::new(pixels(4, 3), 4, 3)
+            .superpixel_count(2)
+            .mask(Mask::from_fn(4, 3, |_, _| false))
+            .build();
+        let too_small = SolverBuilder::new(pixels(4, 3), 4, 3)
+            .superpixel_count(3)
+            .mask(Mask::from_fn(4, 3, |x, y| x == 0 && y < 2))
+            .build();
+
+        // Assert
+        for result in [wrong_size, empty, too_small] {
+            assert!(matches!(result, Err(Error::InvalidParameters(_))));
+        }
+    }
+}
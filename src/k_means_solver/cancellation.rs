@@ -0,0 +1,42 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Flag to abort a running solver from another thread.
+/// Clones share the flag, so keep one and hand the other to the solver.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    /// Asks the solver to stop as soon as possible, this can't be undone
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::k_means_solver::CancellationToken;
+
+    #[test]
+    fn clones_should_share_the_flag() {
+        // Arrange
+        let token = CancellationToken::new();
+        let clone = token.clone();
+
+        // Act
+        clone.cancel();
+
+        // Assert
+        assert!(token.is_cancelled());
+    }
+}
@@ -0,0 +1,1463 @@
+pub use self::builder::SolverBuilder;
+pub use self::cancellation::CancellationToken;
+pub use self::progress::{ProgressEvent, ProgressObserver};
+mod builder;
+mod cancellation;
+mod progress;
+
+use crate::colors::{ClusterColor, ColorCieLab, ColorDifference};
+use crate::connectivity;
+use crate::error::{Error, Result};
+use crate::pixels::{Centroid, DistanceModel, Grid, Pixel, Rectangle};
+use crate::seeding::SeedLayout;
+use crate::segmentation::Segmentation;
+use cgmath::{MetricSpace, Point2};
+use image::Rgb;
+use rand::Rng;
+use rayon::prelude::*;
+use rayon::ThreadPool;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Residual below which the solver considers the centroids settled
+pub const DEFAULT_RESIDUAL_THRESHOLD: f32 = 0.5;
+/// Iteration limit for [`KMeansSuperPixelSolver::solve`], 10 is enough for most images
+pub const DEFAULT_MAX_ITERATIONS: usize = 10;
+/// Size of the square neighborhood seeds are moved in by [`KMeansSuperPixelSolver::perturb_seeds`]
+pub const DEFAULT_SEED_NEIGHBORHOOD: u32 = 3;
+/// Fraction of S² below which [`KMeansSuperPixelSolver::enforce_connectivity`] merges fragments
+pub const DEFAULT_MIN_SIZE_FRACTION: f32 = 0.25;
+/// Seed of the random number generator, unless [`SolverBuilder::rng_seed`] sets another one
+pub const DEFAULT_RNG_SEED: u64 = 0;
+
+struct LabelPixel<C> {
+    pixel: Pixel<C>,
+    /// CIE Lab color of the pixel, converted once while a color difference is set
+    lab: Option<ColorCieLab>,
+    centroid_index: usize,
+    /// left out of clustering, so it is never assigned
+    excluded: bool,
+}
+
+impl<C: ClusterColor> LabelPixel<C> {
+    pub const fn new(pixel: Pixel<C>) -> LabelPixel<C> {
+        LabelPixel {
+            pixel,
+            lab: None,
+            centroid_index: usize::MAX, // this will panic if pixel is unassigned
+            excluded: false,
+        }
+    }
+
+    pub fn pixel(&self) -> &Pixel<C> {
+        &self.pixel
+    }
+
+    fn is_assigned(&self) -> bool {
+        self.centroid_index != usize::MAX
+    }
+}
+
+/// Closest centroid an assignment pass has found for a pixel so far
+#[derive(Clone, Copy)]
+struct Assignment {
+    distance: f32,
+    centroid_index: usize,
+}
+
+impl Assignment {
+    fn try_assign(&mut self, centroid_index: usize, distance: f32) {
+        if self.distance < distance {
+            return;
+        }
+
+        self.distance = distance;
+        self.centroid_index = centroid_index;
+    }
+}
+
+/// How color distance is weighed against spatial distance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compactness {
+    /// a global compactness value m, between 1 and 20, 10 is a good value
+    Fixed(u8),
+    /// SLICO: every cluster normalizes color distance by the largest color distance
+    /// observed in it during the previous iteration, so there is nothing to tune
+    Adaptive,
+}
+
+impl From<u8> for Compactness {
+    fn from(m: u8) -> Self {
+        Compactness::Fixed(m)
+    }
+}
+
+/// Why a [`KMeansSuperPixelSolver::solve_until`] run stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// the residual dropped below the threshold
+    Converged,
+    /// the iteration limit was reached
+    MaxIterations,
+    /// the [`CancellationToken`] of the solver was cancelled
+    Cancelled,
+    /// the time budget of the solver ran out
+    TimeBudgetExceeded,
+}
+
+/// Summary of a [`KMeansSuperPixelSolver::solve_until`] run
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolveReport {
+    iterations: usize,
+    residual: f32,
+    residual_history: Vec<f32>,
+    stop_reason: StopReason,
+}
+
+impl SolveReport {
+    /// number of ticks that were run
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+    /// residual of the last tick, or `f32::MAX` if no tick was run
+    pub fn residual(&self) -> f32 {
+        self.residual
+    }
+    /// residual of every tick, in order
+    pub fn residual_history(&self) -> &[f32] {
+        &self.residual_history
+    }
+    /// true if the residual dropped below the threshold before the iteration limit
+    pub fn converged(&self) -> bool {
+        self.stop_reason == StopReason::Converged
+    }
+    pub fn stop_reason(&self) -> StopReason {
+        self.stop_reason
+    }
+    /// true if the run was cancelled or ran out of time. The labels are those of the
+    /// last complete assignment pass, an interrupted pass leaves them unchanged.
+    pub fn cut_short(&self) -> bool {
+        matches!(
+            self.stop_reason,
+            StopReason::Cancelled | StopReason::TimeBudgetExceeded
+        )
+    }
+}
+
+/// Clusters pixels into superpixels in the color space `C`, CIE Lab by default
+pub struct KMeansSuperPixelSolver<C: ClusterColor = ColorCieLab> {
+    /// pixels in row-major order, so the pixel at (x, y) lives at `width * y + x`
+    flat_pixels: Vec<LabelPixel<C>>,
+    centroids: Vec<Centroid<C>>,
+
+    /// image height
+    height: usize,
+    /// image width
+    width: usize,
+    /// compactness of super pixels
+    compactness: Compactness,
+    /// how color and spatial distance are combined
+    distance_model: DistanceModel,
+    /// perceptual color difference used instead of the euclidean distance in `C`
+    color_difference: Option<ColorDifference>,
+    /// squared color distance every cluster is normalized by in adaptive mode
+    max_color_distances: Vec<f32>,
+    /// edge length (in pixels) of a super pixel, the grid interval S
+    s: f32,
+    /// number of clusters (in this case superpixels)
+    k: usize,
+    /// number of clusters that was asked for
+    requested_k: usize,
+    /// [`Self::solve`] stops once the residual drops below this
+    residual_threshold: f32,
+    /// [`Self::solve`] stops after this many ticks
+    max_iterations: usize,
+    /// fragments smaller than this fraction of S² are merged after [`Self::solve`]
+    min_size_fraction: Option<f32>,
+    /// pool parallel work runs on, the global rayon pool if `None`
+    thread_pool: Option<Arc<ThreadPool>>,
+    /// receives progress events, besides the `log` facade
+    observer: Option<Arc<dyn ProgressObserver>>,
+    /// ticks stop early once this is cancelled
+    cancellation: Option<CancellationToken>,
+    /// wall-clock time a [`Self::solve_until`] run may take
+    time_budget: Option<Duration>,
+    /// end of the time budget of the running [`Self::solve_until`]
+    deadline: Option<Instant>,
+}
+
+impl<C: ClusterColor> KMeansSuperPixelSolver<C> {
+    /// Number of superpixels the solver currently works with. This can differ from
+    /// [`Self::requested_superpixel_count`] after seeding or connectivity enforcement.
+    pub fn superpixel_count(&self) -> usize {
+        self.k
+    }
+
+    pub fn requested_superpixel_count(&self) -> usize {
+        self.requested_k
+    }
+
+    /// Edge length S of a superpixel, in pixels
+    pub fn superpixel_size(&self) -> f32 {
+        self.s
+    }
+
+    pub fn compactness(&self) -> Compactness {
+        self.compactness
+    }
+
+    pub fn distance_model(&self) -> &DistanceModel {
+        &self.distance_model
+    }
+
+    /// Sets how color and spatial distance are combined, starting with the next tick
+    pub fn set_distance_model(&mut self, model: DistanceModel) {
+        self.distance_model = model;
+    }
+
+    pub fn color_difference(&self) -> Option<ColorDifference> {
+        self.color_difference
+    }
+
+    /// Sets the perceptual color difference pixels are compared by, starting with the
+    /// next tick. `None` uses the euclidean distance in the color space of the pixels.
+    pub fn set_color_difference(&mut self, difference: Option<ColorDifference>) {
+        self.color_difference = difference;
+        self.update_pixel_labs();
+        // distances measured on the previous scale would skew the next tick
+        self.max_color_distances = vec![self.initial_max_color_distance(); self.k];
+    }
+
+    /// Squared color distance SLICO normalizes by before clusters have been measured,
+    /// on the scale of the color space or, with a color difference, of CIE Lab
+    fn initial_max_color_distance(&self) -> f32 {
+        match self.color_difference {
+            Some(_) => ColorCieLab::INITIAL_MAX_COLOR_DISTANCE,
+            None => C::INITIAL_MAX_COLOR_DISTANCE,
+        }
+    }
+
+    /// Converts the pixels to CIE Lab once, while a color difference compares them there
+    fn update_pixel_labs(&mut self) {
+        self.install(|solver| {
+            let convert = solver.color_difference.is_some();
+            solver.flat_pixels.par_iter_mut().for_each(|p| {
+                p.lab = match convert {
+                    true => Some(p.lab.take().unwrap_or_else(|| p.pixel.color().to_lab())),
+                    false => None,
+                };
+            });
+        });
+    }
+
+    /// CIE Lab colors of the centroids while a color difference is set, so a pass
+    /// converts every centroid only once
+    fn centroid_labs(&self) -> Vec<ColorCieLab> {
+        match self.color_difference {
+            Some(_) => self.centroids.iter().map(|c| c.color().to_lab()).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Squared color distance of a pixel to centroid `j`. A color difference is measured
+    /// in CIE Lab, with the centroid as reference.
+    fn color_distance2(
+        &self,
+        pixel: &LabelPixel<C>,
+        j: usize,
+        centroid_labs: &[ColorCieLab],
+    ) -> f32 {
+        match self.color_difference {
+            Some(difference) => {
+                let lab = pixel
+                    .lab
+                    .as_ref()
+                    .expect("pixels are converted with the difference");
+                difference.delta_e(&centroid_labs[j], lab).powi(2)
+            }
+            None => pixel.pixel().color().distance2(self.centroids[j].color()),
+        }
+    }
+
+    /// Distance of a pixel to centroid `j`. SLIC weighs the color distance against the
+    /// spatial distance by the compactness m, `sqrt(dc² + (ds / S)² m²)` with
+    /// [`DistanceModel::Slic`]. SLICO normalizes the color distance by the largest one
+    /// measured in the cluster instead, `sqrt(dc² / max_color_distance + ds² / S²)`.
+    fn distance(&self, pixel: &LabelPixel<C>, j: usize, centroid_labs: &[ColorCieLab]) -> f32 {
+        let color_distance = self.color_distance2(pixel, j, centroid_labs);
+        let spatial_distance = pixel
+            .pixel()
+            .values()
+            .distance2(self.centroids[j].position());
+
+        match self.compactness {
+            Compactness::Fixed(m) => {
+                self.distance_model
+                    .combine(color_distance, spatial_distance, m as f32, self.s)
+            }
+            Compactness::Adaptive => self.distance_model.combine(
+                color_distance / self.max_color_distances[j],
+                spatial_distance,
+                1.0,
+                self.s,
+            ),
+        }
+    }
+
+    /// Sets the observer progress events are sent to, starting with the next tick
+    pub fn set_observer(&mut self, observer: impl ProgressObserver + 'static) {
+        self.observer = Some(Arc::new(observer));
+    }
+
+    /// Sets the token that aborts [`Self::solve_tick`] and [`Self::solve_until`]
+    pub fn set_cancellation_token(&mut self, token: CancellationToken) {
+        self.cancellation = Some(token);
+    }
+
+    /// Limits the wall-clock time of every [`Self::solve_until`] run, `None` removes the limit
+    pub fn set_time_budget(&mut self, budget: Option<Duration>) {
+        self.time_budget = budget;
+    }
+
+    /// Why the current tick has to stop early, if it has to
+    fn interruption(&self) -> Option<StopReason> {
+        Self::check_interruption(self.cancellation.as_ref(), self.deadline)
+    }
+
+    fn check_interruption(
+        cancellation: Option<&CancellationToken>,
+        deadline: Option<Instant>,
+    ) -> Option<StopReason> {
+        if cancellation.is_some_and(|t| t.is_cancelled()) {
+            return Some(StopReason::Cancelled);
+        }
+        if deadline.is_some_and(|d| Instant::now() >= d) {
+            return Some(StopReason::TimeBudgetExceeded);
+        }
+
+        None
+    }
+
+    /// Logs an event and passes it on to the observer
+    fn report(&self, event: ProgressEvent) {
+        log::debug!("{:?}", event);
+
+        if let Some(observer) = &self.observer {
+            observer.on_progress(&event);
+        }
+    }
+
+    /// Creates a solver with seeds on a square grid, no seed perturbation and no
+    /// connectivity enforcement. Use [`SolverBuilder`] for everything else.
+    pub fn new(
+        pixels: Vec<Pixel<C>>,
+        compactness: impl Into<Compactness>,
+        superpixel_count: usize,
+        image_width: usize,
+        image_height: usize,
+    ) -> Result<KMeansSuperPixelSolver<C>> {
+        SolverBuilder::new(pixels, image_width, image_height)
+            .superpixel_count(superpixel_count)
+            .compactness(compactness)
+            .seed_neighborhood(None)
+            .connectivity(None)
+            .build()
+    }
+
+    /// Places pixels by their own position, as they may come in any order. Sub-pixel
+    /// positions go to the closest pixel center, but keep their exact position for clustering.
+    fn place_pixels(
+        pixels: Vec<Pixel<C>>,
+        image_width: usize,
+        image_height: usize,
+    ) -> Result<Vec<LabelPixel<C>>> {
+        let pixel_count = image_width * image_height;
+        if pixels.len() != pixel_count {
+            return Err(Error::InvalidParameters(format!(
+                "expected {} pixels for a {}x{} image, got {}",
+                pixel_count,
+                image_width,
+                image_height,
+                pixels.len()
+            )));
+        }
+
+        let mut slots: Vec<Option<LabelPixel<C>>> = (0..pixel_count).map(|_| None).collect();
+        for pixel in pixels {
+            let (x, y) = (pixel.x().round(), pixel.y().round());
+            // written so NaN positions fail as well
+            if !(x >= 0.0 && y >= 0.0 && x < image_width as f32 && y < image_height as f32) {
+                return Err(Error::InvalidParameters(format!(
+                    "pixel ({}, {}) lies outside of the image",
+                    pixel.x(),
+                    pixel.y()
+                )));
+            }
+            let (x, y) = (x as usize, y as usize);
+
+            slots[Self::get_index(x, y, image_width)] = Some(LabelPixel::new(pixel));
+        }
+
+        // as many pixels as slots were given, so a missing pixel means another one was repeated
+        slots.into_iter().collect::<Option<_>>().ok_or_else(|| {
+            Error::InvalidParameters(String::from("every pixel needs to be given exactly once"))
+        })
+    }
+
+    fn get_index(x: usize, y: usize, width: usize) -> usize {
+        width * y + x
+    }
+
+    /// Grid interval `S = sqrt(N / k)` of `superpixel_count` superpixels spread over `pixel_count` pixels
+    fn calculate_superpixel_size(pixel_count: usize, superpixel_count: usize) -> f32 {
+        (pixel_count as f64 / superpixel_count.max(1) as f64).sqrt() as f32
+    }
+
+    /// Places `self.k` seeds and derives S from the number of seeds actually placed.
+    /// With pixels left out, seeds are spread over the bounding box of the clustered
+    /// pixels and only those landing on clustered pixels are kept.
+    fn calculate_initial_centroids<R: Rng + ?Sized>(&mut self, layout: SeedLayout, rng: &mut R) {
+        let clustered: Vec<usize> = (0..self.flat_pixels.len())
+            .filter(|i| !self.flat_pixels[*i].excluded)
+            .collect();
+        let position = |i: usize| ((i % self.width) as u32, (i / self.width) as u32);
+
+        let (min, max) = clustered
+            .iter()
+            .map(|i| position(*i))
+            .fold(((u32::MAX, u32::MAX), (0, 0)), |(min, max), (x, y)| {
+                ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))
+            });
+        let rect = Rectangle::new(max.0 - min.0 + 1, max.1 - min.1 + 1);
+        let area = rect.width() as usize * rect.height() as usize;
+        // as many seeds over the bounding box as keep the density of k over the clustered pixels
+        let n = ((self.k * area) as f64 / clustered.len() as f64).round() as usize;
+
+        let mut seeds: Vec<usize> = layout
+            .sample(&rect, n.clamp(1, area) as u32, rng)
+            .iter()
+            .map(|p| Self::get_index((p.x + min.0) as usize, (p.y + min.1) as usize, self.width))
+            .filter(|i| !self.flat_pixels[*i].excluded)
+            .collect();
+        if seeds.is_empty() {
+            // the mask is too thin for the layout, start from its middle pixel
+            seeds.push(clustered[clustered.len() / 2]);
+        }
+
+        self.centroids = seeds
+            .iter()
+            .map(|i| Centroid::new_from_pixel(self.flat_pixels[*i].pixel()))
+            .collect();
+
+        self.k = self.centroids.len();
+        self.s = Self::calculate_superpixel_size(clustered.len(), self.k);
+        self.max_color_distances = vec![self.initial_max_color_distance(); self.k];
+    }
+
+    /// Runs `f` on the thread pool of the solver
+    fn install<R: Send>(&mut self, f: impl FnOnce(&mut Self) -> R + Send) -> R {
+        match self.thread_pool.clone() {
+            Some(pool) => pool.install(|| f(self)),
+            None => f(self),
+        }
+    }
+
+    /// Squared color gradient at a pixel, using central differences clamped at the borders
+    fn gradient(&self, x: usize, y: usize) -> f32 {
+        let color = |x: usize, y: usize| {
+            self.flat_pixels[Self::get_index(x, y, self.width)]
+                .pixel()
+                .color()
+        };
+
+        let horizontal =
+            color((x + 1).min(self.width - 1), y).distance2(color(x.saturating_sub(1), y));
+        let vertical =
+            color(x, (y + 1).min(self.height - 1)).distance2(color(x, y.saturating_sub(1)));
+
+        horizontal + vertical
+    }
+
+    /// Moves every seed to the lowest gradient position inside the `neighborhood` x `neighborhood`
+    /// window around it, so superpixels don't start on edges or noisy pixels, and reassigns
+    /// the pixels. The original SLIC paper uses a 3 x 3 neighborhood. The window is centered
+    /// on the seed, so an even `neighborhood` has no center and is rejected.
+    pub fn perturb_seeds(&mut self, neighborhood: u32) -> Result<()> {
+        Self::check_seed_neighborhood(neighborhood)?;
+        let ring_count = neighborhood / 2;
+
+        self.centroids = self
+            .centroids
+            .iter()
+            .map(|c| {
+                let window = Grid::new(
+                    (c.x().round() as u32, c.y().round() as u32),
+                    ring_count,
+                    self.width as u32,
+                    self.height as u32,
+                );
+
+                let mut lowest = (
+                    f32::MAX,
+                    Point2::new(c.x().round() as u32, c.y().round() as u32),
+                );
+                for point in window.points() {
+                    if self.flat_pixels
+                        [Self::get_index(point.x as usize, point.y as usize, self.width)]
+                    .excluded
+                    {
+                        continue;
+                    }
+
+                    let gradient = self.gradient(point.x as usize, point.y as usize);
+                    if gradient < lowest.0 {
+                        lowest = (gradient, point);
+                    }
+                }
+
+                let index = Self::get_index(lowest.1.x as usize, lowest.1.y as usize, self.width);
+                Centroid::new_from_pixel(self.flat_pixels[index].pixel())
+            })
+            .collect();
+
+        self.assign_pixels_to_superpixels();
+
+        Ok(())
+    }
+
+    /// Errors unless `neighborhood` is odd, so its window is centered on the seed
+    pub(crate) fn check_seed_neighborhood(neighborhood: u32) -> Result<()> {
+        match neighborhood % 2 {
+            1 => Ok(()),
+            _ => Err(Error::InvalidParameters(format!(
+                "seed neighborhood needs to be odd to be centered on the seed, got {}",
+                neighborhood
+            ))),
+        }
+    }
+
+    pub fn centroids(&self) -> &[Centroid<C>] {
+        &self.centroids
+    }
+
+    fn pixels(&self) -> &Vec<LabelPixel<C>> {
+        &self.flat_pixels
+    }
+
+    /// Pixels taking part in clustering, in row-major order
+    fn clustered_pixels(&self) -> impl Iterator<Item = &LabelPixel<C>> {
+        self.flat_pixels.iter().filter(|p| !p.excluded)
+    }
+
+    /// Search window of a centroid, reaching `s` pixels in every direction (2S x 2S)
+    fn search_window(&self, centroid: &Centroid<C>) -> Grid {
+        Grid::new(
+            (centroid.x().round() as u32, centroid.y().round() as u32),
+            self.s.ceil() as u32,
+            self.width as u32,
+            self.height as u32,
+        )
+    }
+
+    /// Labels every pixel with its closest centroid. Returns false if the pass was
+    /// interrupted, the pixels keep the labels of the last complete pass then.
+    fn assign_pixels_to_superpixels(&mut self) -> bool {
+        let committed = self.install(Self::assign_pixels_in_windows);
+
+        if committed && self.compactness == Compactness::Adaptive {
+            self.update_max_color_distances();
+        }
+
+        committed
+    }
+
+    fn assign_pixels_in_windows(&mut self) -> bool {
+        let centroids = &self.centroids;
+        let centroid_labs = self.centroid_labs();
+        let distance = |pixel: &LabelPixel<C>, j: usize| self.distance(pixel, j, &centroid_labs);
+        let windows: Vec<Grid> = centroids.iter().map(|c| self.search_window(c)).collect();
+        let cancellation = self.cancellation.as_ref();
+        let deadline = self.deadline;
+        // the first pass, before there are any labels to keep, is always finished
+        let labeled = self.flat_pixels.iter().any(|p| p.is_assigned());
+        let interrupted = || labeled && Self::check_interruption(cancellation, deadline).is_some();
+
+        // labels are collected next to the pixels and only committed once the pass is complete
+        let mut assignments: Vec<Assignment> = self
+            .flat_pixels
+            .iter()
+            .map(|p| Assignment {
+                distance: f32::MAX,
+                centroid_index: p.centroid_index,
+            })
+            .collect();
+
+        // Every row is visited by the centroids whose search window overlaps it,
+        // so rows can be labeled in parallel and every pixel only sees nearby centroids
+        assignments
+            .par_chunks_mut(self.width)
+            .zip(self.flat_pixels.par_chunks(self.width))
+            .enumerate()
+            .for_each(|(y, (assignments, row))| {
+                let y = y as u32;
+                if interrupted() {
+                    return;
+                }
+
+                for (j, window) in windows.iter().enumerate() {
+                    if !window.y_range().contains(&y) {
+                        continue;
+                    }
+
+                    for x in window.x_range() {
+                        let p = &row[x as usize];
+                        if !p.excluded {
+                            assignments[x as usize].try_assign(j, distance(p, j));
+                        }
+                    }
+                }
+            });
+
+        if interrupted() {
+            return false;
+        }
+
+        // Centroids drifting apart can leave gaps no window reaches. Those pixels keep
+        // their previous label, or fall back to the globally closest centroid.
+        assignments
+            .par_iter_mut()
+            .zip(self.flat_pixels.par_iter())
+            .filter(|(a, p)| a.centroid_index == usize::MAX && !p.excluded)
+            .for_each(|(a, p)| {
+                for j in 0..centroids.len() {
+                    a.try_assign(j, distance(p, j));
+                }
+            });
+
+        for (p, a) in self.flat_pixels.iter_mut().zip(assignments) {
+            p.centroid_index = a.centroid_index;
+        }
+
+        true
+    }
+
+    /// Measures the largest squared color distance between every centroid and its pixels
+    fn update_max_color_distances(&mut self) {
+        let mut max_color_distances = vec![0.0_f32; self.k];
+        let centroid_labs = self.centroid_labs();
+        for pixel in self.clustered_pixels() {
+            let distance = self.color_distance2(pixel, pixel.centroid_index, &centroid_labs);
+            let max = &mut max_color_distances[pixel.centroid_index];
+            *max = max.max(distance);
+        }
+
+        // an empty or uniform cluster would divide by zero, so it keeps its previous value
+        for (current, measured) in self.max_color_distances.iter_mut().zip(max_color_distances) {
+            if measured > 0.0 {
+                *current = measured;
+            }
+        }
+    }
+
+    fn clusters(&self) -> Vec<(&Centroid<C>, Vec<&LabelPixel<C>>)> {
+        let mut clusters: Vec<Vec<&LabelPixel<C>>> = (0..self.k).map(|_| Vec::new()).collect();
+        for pixel in self.clustered_pixels() {
+            clusters[pixel.centroid_index].push(pixel)
+        }
+
+        self.centroids.iter().zip(clusters).collect()
+    }
+
+    /// Moves every centroid to the mean color and position of its cluster.
+    /// Centroids of empty clusters stay where they are.
+    fn update_centroids(&mut self) {
+        // sums are accumulated in f64 and in pixel order, to stay exact and reproducible
+        let means: Vec<Option<Centroid<C>>> = self
+            .clusters()
+            .into_iter()
+            .map(|(_, cluster)| {
+                let color = C::mean(cluster.iter().map(|p| p.pixel().color()))?;
+                let (x, y) = cluster.iter().fold((0.0, 0.0), |(x, y), p| {
+                    (x + p.pixel().x() as f64, y + p.pixel().y() as f64)
+                });
+                let count = cluster.len() as f64;
+
+                Some(Centroid::new_from_color(
+                    color,
+                    (x / count) as f32,
+                    (y / count) as f32,
+                ))
+            })
+            .collect();
+
+        for (centroid, mean) in self.centroids.iter_mut().zip(means) {
+            if let Some(mean) = mean {
+                *centroid = mean;
+            }
+        }
+    }
+
+    /// Root mean square distance the centroids moved in the combined color and position space
+    fn residual(previous: &[Centroid<C>], current: &[Centroid<C>]) -> f32 {
+        if current.is_empty() {
+            return 0.0;
+        }
+
+        let squared_sum: f32 = previous
+            .iter()
+            .zip(current)
+            .map(|(p, c)| p.distance2(c))
+            .sum();
+
+        (squared_sum / current.len() as f32).sqrt()
+    }
+
+    /// Runs a single assignment and update step and returns the residual error
+    /// of the centroid movement. Returns `None` if the tick was interrupted by
+    /// the cancellation token or time budget, the centroids are left untouched then.
+    pub fn solve_tick(&mut self) -> Option<f32> {
+        if self.interruption().is_some() {
+            return None;
+        }
+
+        let previous = self.centroids.clone();
+        if !self.assign_pixels_to_superpixels() {
+            return None;
+        }
+        self.update_centroids();
+
+        Some(Self::residual(&previous, &self.centroids))
+    }
+
+    /// Iterates until the residual drops below the configured threshold or the
+    /// iteration limit is reached, then enforces connectivity if configured.
+    /// Defaults to [`DEFAULT_RESIDUAL_THRESHOLD`] and [`DEFAULT_MAX_ITERATIONS`].
+    /// A cancelled run, or one that has used up its time budget, skips connectivity
+    /// enforcement. Once started, connectivity enforcement runs to completion.
+    pub fn solve(&mut self) -> SolveReport {
+        let start = Instant::now();
+        let report = self.solve_until(self.residual_threshold, self.max_iterations);
+        let out_of_time = self
+            .time_budget
+            .is_some_and(|budget| start.elapsed() >= budget);
+
+        if let (Some(fraction), false) = (
+            self.min_size_fraction,
+            report.stop_reason() == StopReason::Cancelled || out_of_time,
+        ) {
+            self.enforce_connectivity(fraction);
+        }
+
+        report
+    }
+
+    /// Iterates until the residual drops below `threshold` or `max_iterations` ticks have been run,
+    /// stopping early if the cancellation token is cancelled or the time budget runs out
+    pub fn solve_until(&mut self, threshold: f32, max_iterations: usize) -> SolveReport {
+        let mut residual_history = Vec::with_capacity(max_iterations);
+        let mut residual = f32::MAX;
+        let start = Instant::now();
+        self.deadline = self.time_budget.map(|budget| start + budget);
+
+        let stop_reason = loop {
+            if residual < threshold {
+                break StopReason::Converged;
+            }
+            if residual_history.len() >= max_iterations {
+                break StopReason::MaxIterations;
+            }
+
+            residual = match self.solve_tick() {
+                Some(residual) => residual,
+                None => break self.interruption().unwrap_or(StopReason::Cancelled),
+            };
+            self.report(ProgressEvent::Iteration {
+                index: residual_history.len(),
+                residual,
+                elapsed: start.elapsed(),
+            });
+            residual_history.push(residual);
+        };
+        self.deadline = None;
+
+        SolveReport {
+            iterations: residual_history.len(),
+            residual,
+            residual_history,
+            stop_reason,
+        }
+    }
+
+    /// Splits every superpixel into its 4-connected regions and merges regions
+    /// smaller than `min_size_fraction * S²` pixels into their largest adjacent
+    /// region. Afterwards every superpixel is a single connected region, so the
+    /// number of superpixels may change.
+    pub fn enforce_connectivity(&mut self, min_size_fraction: f32) {
+        let min_size = (min_size_fraction * self.s.powi(2)) as usize;
+        let labels: Vec<usize> = self.pixels().iter().map(|p| p.centroid_index).collect();
+
+        let (labels, count) =
+            connectivity::enforce_connectivity(&labels, self.width, self.height, min_size);
+
+        for (pixel, label) in self.flat_pixels.iter_mut().zip(labels) {
+            pixel.centroid_index = label;
+        }
+
+        self.report(ProgressEvent::PostProcessing {
+            superpixel_count_before: self.k,
+            superpixel_count_after: count,
+        });
+        self.k = count;
+        // every new label has pixels, so all of these are replaced by their cluster mean
+        self.centroids = vec![self.centroids[0].clone(); count];
+        self.max_color_distances = vec![self.initial_max_color_distance(); count];
+        self.update_centroids();
+    }
+
+    /// Label map and region statistics of the current superpixels.
+    /// Superpixels without pixels are left out, so labels may be renumbered.
+    pub fn segmentation(&self) -> Segmentation {
+        Segmentation::new(
+            self.width as u32,
+            self.height as u32,
+            self.pixels()
+                .iter()
+                .map(|p| (p.centroid_index, p.pixel().color().to_lab())),
+        )
+    }
+
+    pub fn current_superpixels(&self) -> Vec<Vec<(u32, u32, Rgb<u8>)>> {
+        let mut result: Vec<Vec<(u32, u32, Rgb<u8>)>> = Vec::with_capacity(self.k);
+
+        for (centroid, cluster) in self.clusters() {
+            let color = centroid.color().to_rgb().as_image_rgb();
+            result.push(
+                cluster
+                    .iter()
+                    .map(|c| {
+                        (
+                            c.pixel().x().round() as u32,
+                            c.pixel().y().round() as u32,
+                            color,
+                        )
+                    })
+                    .collect(),
+            );
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::{ClusterColor, ColorCieLab, ColorDifference, ColorOkLab, ColorRgb};
+    use crate::error::Error;
+    use crate::k_means_solver::{
+        CancellationToken, Compactness, KMeansSuperPixelSolver, LabelPixel, ProgressEvent,
+        SolverBuilder, StopReason, DEFAULT_MIN_SIZE_FRACTION, DEFAULT_RESIDUAL_THRESHOLD,
+        DEFAULT_SEED_NEIGHBORHOOD,
+    };
+    use crate::pixels::{Centroid, DistanceModel, Pixel, PixelCieLab};
+    use crate::seeding::SeedLayout;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    fn two_tone_pixels(width: u32, height: u32) -> Vec<PixelCieLab> {
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let color = match x < width / 2 {
+                    true => ColorCieLab::new(20.0, 10.0, -10.0),
+                    false => ColorCieLab::new(80.0, -10.0, 10.0),
+                };
+                PixelCieLab::new(x as f32, y as f32, color)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn distance_should_weigh_color_against_position() {
+        // Arrange
+        let solver = SolverBuilder::new(two_tone_pixels(40, 30), 40, 30)
+            .superpixel_count(12)
+            .compactness(10)
+            .build()
+            .unwrap();
+        let centroid = &solver.centroids()[0];
+        let s = solver.superpixel_size();
+        let at_centroid = PixelCieLab::new(centroid.x(), centroid.y(), centroid.color().clone());
+        let one_s_away = PixelCieLab::new(centroid.x() + s, centroid.y(), centroid.color().clone());
+
+        // Act
+        let zero = solver.distance(&LabelPixel::new(at_centroid), 0, &[]);
+        let m = solver.distance(&LabelPixel::new(one_s_away), 0, &[]);
+
+        // Assert
+        assert_eq!(0.0, zero);
+        assert!((m - 10.0).abs() < 1e-4, "{}", m);
+    }
+
+    #[test]
+    fn every_pixel_should_be_assigned_to_a_nearby_centroid() {
+        // Arrange
+        let (width, height) = (40, 30);
+        let pixels = two_tone_pixels(width, height);
+
+        let mut solver =
+            KMeansSuperPixelSolver::new(pixels, 10, 12, width as usize, height as usize).unwrap();
+        solver.solve_tick();
+        let centroids = solver.centroids().to_vec();
+
+        // Act
+        solver.assign_pixels_to_superpixels();
+
+        // Assert, against the centroids the pixels were assigned to and the window radius
+        let radius = solver.s.ceil();
+        for pixel in solver.pixels() {
+            let centroid = &centroids[pixel.centroid_index];
+            assert!((centroid.x().round() - pixel.pixel().x()).abs() <= radius);
+            assert!((centroid.y().round() - pixel.pixel().y()).abs() <= radius);
+        }
+    }
+
+    #[test]
+    fn solve_until_should_stop_at_max_iterations() {
+        // Arrange
+        let (width, height) = (40, 30);
+        let pixels = two_tone_pixels(width, height);
+        let mut solver =
+            KMeansSuperPixelSolver::new(pixels, 10, 12, width as usize, height as usize).unwrap();
+
+        // Act
+        let report = solver.solve_until(-1.0, 3);
+
+        // Assert
+        assert_eq!(3, report.iterations());
+        assert_eq!(3, report.residual_history().len());
+        assert!(!report.converged());
+    }
+
+    #[test]
+    fn solve_should_converge_on_simple_images() {
+        // Arrange
+        let (width, height) = (40, 30);
+        let pixels = two_tone_pixels(width, height);
+        let mut solver =
+            KMeansSuperPixelSolver::new(pixels, 10, 12, width as usize, height as usize).unwrap();
+
+        // Act
+        let report = solver.solve_until(DEFAULT_RESIDUAL_THRESHOLD, 50);
+
+        // Assert
+        assert!(report.converged());
+        assert!(report.iterations() < 50);
+        assert_eq!(
+            report.residual(),
+            *report.residual_history().last().unwrap()
+        );
+    }
+
+    #[test]
+    fn centroids_should_hold_the_cluster_mean() {
+        // Arrange
+        let (width, height) = (40, 30);
+        let pixels = two_tone_pixels(width, height);
+        let mut solver =
+            KMeansSuperPixelSolver::new(pixels, 10, 12, width as usize, height as usize).unwrap();
+
+        // Act
+        solver.solve_tick();
+
+        // Assert
+        for (centroid, cluster) in solver.clusters().iter().filter(|(_, c)| !c.is_empty()) {
+            let count = cluster.len() as f32;
+            let mean_x = cluster.iter().map(|p| p.pixel().x()).sum::<f32>() / count;
+            let mean_l = cluster.iter().map(|p| p.pixel().color().l()).sum::<f32>() / count;
+
+            assert!((centroid.x() - mean_x).abs() < 1e-3);
+            assert!((centroid.l() - mean_l).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn superpixels_should_be_connected_after_enforcing_connectivity() {
+        // Arrange
+        let (width, height) = (40, 30);
+        let pixels = two_tone_pixels(width, height);
+        let mut solver =
+            KMeansSuperPixelSolver::new(pixels, 10, 12, width as usize, height as usize).unwrap();
+        solver.solve();
+
+        // Act
+        solver.enforce_connectivity(DEFAULT_MIN_SIZE_FRACTION);
+
+        // Assert
+        let labels: Vec<usize> = solver.pixels().iter().map(|p| p.centroid_index).collect();
+        let (_, count) =
+            crate::connectivity::enforce_connectivity(&labels, width as usize, height as usize, 0);
+        assert_eq!(solver.k, count);
+        assert!(solver.clusters().iter().all(|(_, c)| !c.is_empty()));
+    }
+
+    #[test]
+    fn perturbed_seeds_should_move_off_edges() {
+        // Arrange
+        let (width, height) = (40, 30);
+        let pixels = two_tone_pixels(width, height);
+        let mut solver =
+            KMeansSuperPixelSolver::new(pixels, 10, 12, width as usize, height as usize).unwrap();
+        solver.centroids[0] = Centroid::new_from_pixel(solver.pixels()[20 + 5 * 40].pixel());
+
+        // Act
+        solver.perturb_seeds(DEFAULT_SEED_NEIGHBORHOOD).unwrap();
+
+        // Assert
+        let seed = &solver.centroids()[0];
+        assert!((seed.y() - 5.0).abs() <= 1.0);
+        assert!(seed.x() < 19.0 || seed.x() > 20.0);
+        assert_eq!(0.0, solver.gradient(seed.x() as usize, seed.y() as usize));
+    }
+
+    #[test]
+    fn even_seed_neighborhoods_should_error() {
+        // Arrange
+        let (width, height) = (40, 30);
+        let pixels = two_tone_pixels(width, height);
+        let mut solver =
+            KMeansSuperPixelSolver::new(pixels, 10, 12, width as usize, height as usize).unwrap();
+        let centroids = solver.centroids().to_vec();
+
+        // Act
+        let result = solver.perturb_seeds(4);
+
+        // Assert
+        assert!(matches!(result, Err(Error::InvalidParameters(_))));
+        assert_eq!(centroids, solver.centroids());
+    }
+
+    #[test]
+    fn adaptive_compactness_should_measure_every_cluster() {
+        // Arrange
+        let (width, height) = (40, 30);
+        let pixels = two_tone_pixels(width, height);
+        let mut solver = KMeansSuperPixelSolver::new(
+            pixels,
+            Compactness::Adaptive,
+            12,
+            width as usize,
+            height as usize,
+        )
+        .unwrap();
+
+        // Act
+        let report = solver.solve_until(DEFAULT_RESIDUAL_THRESHOLD, 50);
+
+        // Assert
+        assert!(report.converged());
+        assert_eq!(solver.k, solver.max_color_distances.len());
+        assert!(solver.max_color_distances.iter().all(|d| *d > 0.0));
+    }
+
+    #[test]
+    fn legacy_distance_model_should_still_solve() {
+        // Arrange
+        let (width, height) = (40, 30);
+        let pixels = two_tone_pixels(width, height);
+        let mut solver =
+            KMeansSuperPixelSolver::new(pixels, 10, 12, width as usize, height as usize).unwrap();
+        solver.set_distance_model(DistanceModel::Legacy);
+
+        // Act
+        let report = solver.solve_until(DEFAULT_RESIDUAL_THRESHOLD, 50);
+
+        // Assert
+        assert!(report.converged());
+    }
+
+    #[test]
+    fn superpixel_size_should_not_truncate() {
+        // Act
+        let small = <KMeansSuperPixelSolver>::calculate_superpixel_size(1200, 12);
+        let large = <KMeansSuperPixelSolver>::calculate_superpixel_size(100_000 * 100_000, 1);
+
+        // Assert
+        assert!((small - 10.0).abs() < 1e-5);
+        assert!((large - 100_000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn seed_count_should_match_request() {
+        // Arrange
+        let (width, height) = (40, 30);
+        let pixels = two_tone_pixels(width, height);
+
+        // Act
+        let solver =
+            KMeansSuperPixelSolver::new(pixels, 10, 12, width as usize, height as usize).unwrap();
+
+        // Assert
+        assert_eq!(12, solver.requested_superpixel_count());
+        assert_eq!(12, solver.superpixel_count());
+        assert_eq!(12, solver.centroids().len());
+        assert!((solver.superpixel_size() - 10.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn invalid_parameters_should_error() {
+        // Arrange
+        let (width, height) = (4, 3);
+
+        // Act
+        let no_superpixels =
+            KMeansSuperPixelSolver::new(two_tone_pixels(width, height), 10, 0, 4, 3);
+        let too_many_superpixels =
+            KMeansSuperPixelSolver::new(two_tone_pixels(width, height), 10, 13, 4, 3);
+        let wrong_size = KMeansSuperPixelSolver::new(two_tone_pixels(width, height), 10, 2, 4, 4);
+        let mut repeated = two_tone_pixels(width, height);
+        repeated[0] = PixelCieLab::new(1.0, 0.0, ColorCieLab::new(0.0, 0.0, 0.0));
+        let repeated = KMeansSuperPixelSolver::new(repeated, 10, 2, 4, 3);
+
+        // Assert
+        for result in [no_superpixels, too_many_superpixels, wrong_size, repeated] {
+            assert!(matches!(result, Err(Error::InvalidParameters(_))));
+        }
+    }
+
+    #[test]
+    fn segmentation_should_match_labels() {
+        // Arrange
+        let (width, height) = (40, 30);
+        let pixels = two_tone_pixels(width, height);
+        let mut solver =
+            KMeansSuperPixelSolver::new(pixels, 10, 12, width as usize, height as usize).unwrap();
+        solver.solve();
+
+        // Act
+        let segmentation = solver.segmentation();
+
+        // Assert
+        let mut labels = vec![None; solver.superpixel_count()];
+        for pixel in solver.pixels() {
+            let label = segmentation.label(pixel.pixel().x() as u32, pixel.pixel().y() as u32);
+            let expected = labels[pixel.centroid_index].get_or_insert(label);
+            assert_eq!(*expected, label);
+        }
+        let pixel_count: usize = segmentation.regions().map(|r| r.pixel_count()).sum();
+        assert_eq!(solver.pixels().len(), pixel_count);
+    }
+
+    #[test]
+    fn superpixels_should_cover_the_whole_image() {
+        // Arrange
+        let (width, height) = (40, 30);
+        let pixels = two_tone_pixels(width, height);
+
+        // Act
+        let solver =
+            KMeansSuperPixelSolver::new(pixels, 10, 12, width as usize, height as usize).unwrap();
+        let superpixels = solver.current_superpixels();
+
+        // Assert
+        let covered: usize = superpixels.iter().map(|s| s.len()).sum();
+        assert_eq!((width * height) as usize, covered);
+    }
+
+    #[test]
+    fn observer_should_receive_progress_events() {
+        // Arrange
+        let (width, height) = (40, 30);
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+
+        // Act
+        let mut solver = SolverBuilder::new(two_tone_pixels(width, height), 40, 30)
+            .superpixel_count(12)
+            .max_iterations(3)
+            .residual_threshold(-1.0)
+            .observer(move |e: &ProgressEvent| sink.lock().unwrap().push(e.clone()))
+            .build()
+            .unwrap();
+        solver.solve();
+
+        // Assert
+        let events = events.lock().unwrap();
+        assert_eq!(5, events.len());
+        assert!(matches!(events[0], ProgressEvent::Seeded { .. }));
+        for (i, event) in events[1..4].iter().enumerate() {
+            assert!(matches!(event, ProgressEvent::Iteration { index, .. } if *index == i));
+        }
+        assert!(matches!(events[4], ProgressEvent::PostProcessing { .. }));
+    }
+
+    #[test]
+    fn cancelling_should_stop_after_the_current_tick() {
+        // Arrange
+        let (width, height) = (40, 30);
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        let mut solver = SolverBuilder::new(two_tone_pixels(width, height), 40, 30)
+            .superpixel_count(12)
+            .residual_threshold(-1.0)
+            .cancellation_token(token)
+            .observer(move |e: &ProgressEvent| {
+                if let ProgressEvent::Iteration { index: 1, .. } = e {
+                    canceller.cancel();
+                }
+            })
+            .build()
+            .unwrap();
+
+        // Act
+        let report = solver.solve();
+
+        // Assert
+        assert_eq!(StopReason::Cancelled, report.stop_reason());
+        assert!(report.cut_short());
+        assert_eq!(2, report.iterations());
+        assert_eq!(None, solver.solve_tick());
+        assert_eq!(
+            (width * height) as usize,
+            solver.segmentation().labels().len()
+        );
+    }
+
+    #[test]
+    fn interrupted_assignment_should_keep_the_previous_labels() {
+        // Arrange
+        let (width, height) = (40, 30);
+        let token = CancellationToken::new();
+        let mut solver = SolverBuilder::new(two_tone_pixels(width, height), 40, 30)
+            .superpixel_count(12)
+            .cancellation_token(token.clone())
+            .build()
+            .unwrap();
+        solver.solve_tick();
+        let labels = solver.segmentation().labels().to_vec();
+        for centroid in solver.centroids.iter_mut() {
+            *centroid = Centroid::new_from_color(centroid.color().clone(), 0.0, 0.0);
+        }
+        token.cancel();
+
+        // Act
+        let committed = solver.assign_pixels_to_superpixels();
+
+        // Assert
+        assert!(!committed);
+        assert_eq!(labels, solver.segmentation().labels());
+    }
+
+    #[test]
+    fn exhausted_time_budget_should_stop_solving() {
+        // Arrange
+        let (width, height) = (40, 30);
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let mut solver = SolverBuilder::new(two_tone_pixels(width, height), 40, 30)
+            .superpixel_count(12)
+            .time_budget(Duration::from_secs(0))
+            .observer(move |e: &ProgressEvent| sink.lock().unwrap().push(e.clone()))
+            .build()
+            .unwrap();
+
+        // Act
+        let report = solver.solve();
+
+        // Assert
+        assert_eq!(StopReason::TimeBudgetExceeded, report.stop_reason());
+        assert_eq!(0, report.iterations());
+        assert!(solver.superpixel_count() > 0);
+        let events = events.lock().unwrap();
+        assert!(!events
+            .iter()
+            .any(|e| matches!(e, ProgressEvent::PostProcessing { .. })));
+    }
+
+    #[test]
+    fn labels_should_not_depend_on_thread_count() {
+        // Arrange
+        let (width, height) = (64, 48);
+        let solve = |threads: usize| {
+            let pixels = (0..width * height)
+                .map(|i| {
+                    let (x, y) = (i % width, i / width);
+                    let l = ((x * 7 + y * 13) % 100) as f32;
+                    PixelCieLab::new(
+                        x as f32,
+                        y as f32,
+                        ColorCieLab::new(l, x as f32 - 32.0, y as f32),
+                    )
+                })
+                .collect();
+            let mut solver = SolverBuilder::new(pixels, width as usize, height as usize)
+                .superpixel_count(30)
+                .compactness(Compactness::Adaptive)
+                .seed_layout(SeedLayout::PoissonDisk)
+                .rng_seed(1234)
+                .thread_count(threads)
+                .build()
+                .unwrap();
+            solver.solve();
+            solver.segmentation()
+        };
+
+        // Act
+        let single = solve(1);
+        let parallel = solve(4);
+
+        // Assert
+        assert_eq!(single.labels(), parallel.labels());
+        for (a, b) in single.regions().zip(parallel.regions()) {
+            assert_eq!(a.mean_lab().values(), b.mean_lab().values());
+        }
+    }
+
+    fn solve_in<C: ClusterColor>() -> Vec<u32> {
+        let (width, height) = (40, 30);
+        let pixels: Vec<Pixel<C>> = two_tone_pixels(width, height)
+            .into_iter()
+            .map(|p| Pixel::new(p.x(), p.y(), C::from_xyz(&p.color().as_xyz())))
+            .collect();
+        let mut solver = SolverBuilder::new(pixels, width as usize, height as usize)
+            .superpixel_count(12)
+            .build()
+            .unwrap();
+        solver.solve();
+
+        solver.segmentation().labels().to_vec()
+    }
+
+    #[test]
+    fn superpixels_should_follow_edges_in_every_color_space() {
+        // Act
+        let labelings = [
+            solve_in::<ColorCieLab>(),
+            solve_in::<ColorOkLab>(),
+            solve_in::<ColorRgb>(),
+        ];
+
+        // Assert
+        for labels in labelings.iter() {
+            for row in labels.chunks(40) {
+                assert!(row[..20].iter().all(|l| !row[20..].contains(l)));
+            }
+        }
+    }
+
+    #[test]
+    fn initial_max_color_distance_should_match_the_color_space() {
+        // Arrange
+        let pixels: Vec<Pixel<ColorOkLab>> = two_tone_pixels(40, 30)
+            .iter()
+            .map(|p| Pixel::new(p.x(), p.y(), ColorOkLab::from_xyz(&p.color().as_xyz())))
+            .collect();
+        let mut solver = SolverBuilder::new(pixels, 40, 30)
+            .superpixel_count(12)
+            .compactness(Compactness::Adaptive)
+            .build()
+            .unwrap();
+
+        // Act
+        solver.set_color_difference(Some(ColorDifference::Ciede2000));
+        let lab_scale = solver.max_color_distances.clone();
+        solver.set_color_difference(None);
+        let ok_lab_scale = solver.max_color_distances.clone();
+
+        // Assert
+        assert!(lab_scale.iter().all(|d| *d == 100.0));
+        assert!(ok_lab_scale.iter().all(|d| *d == 0.01));
+    }
+
+    #[test]
+    fn pixels_should_be_converted_to_lab_only_with_a_color_difference() {
+        // Arrange
+        let mut solver = SolverBuilder::new(two_tone_pixels(40, 30), 40, 30)
+            .superpixel_count(12)
+            .build()
+            .unwrap();
+        let converted =
+            |solver: &KMeansSuperPixelSolver| solver.pixels().iter().all(|p| p.lab.is_some());
+        let unconverted =
+            |solver: &KMeansSuperPixelSolver| solver.pixels().iter().all(|p| p.lab.is_none());
+        assert!(unconverted(&solver));
+
+        // Act & Assert
+        solver.set_color_difference(Some(ColorDifference::Cie76));
+        assert!(converted(&solver));
+        assert_eq!(
+            Some(&ColorCieLab::new(20.0, 10.0, -10.0)),
+            solver.pixels()[0].lab.as_ref()
+        );
+        solver.set_color_difference(None);
+        assert!(unconverted(&solver));
+    }
+
+    #[test]
+    fn superpixels_should_follow_edges_with_every_color_difference() {
+        let differences = [
+            ColorDifference::Cie76,
+            ColorDifference::Cie94GraphicArts,
+            ColorDifference::Ciede2000,
+            ColorDifference::Cmc {
+                lightness: 2.0,
+                chroma: 1.0,
+            },
+        ];
+
+        for difference in differences.iter() {
+            // Arrange
+            let (width, height) = (40, 30);
+            let mut solver = SolverBuilder::new(two_tone_pixels(width, height), 40, 30)
+                .superpixel_count(12)
+                .compactness(Compactness::Adaptive)
+                .color_difference(*difference)
+                .build()
+                .unwrap();
+
+            // Act
+            solver.solve();
+
+            // Assert
+            let segmentation = solver.segmentation();
+            for row in segmentation.labels().chunks(40) {
+                assert!(row[..20].iter().all(|l| !row[20..].contains(l)));
+            }
+        }
+    }
+
+    #[test]
+    fn sub_pixel_positions_should_be_clustered_exactly() {
+        // Arrange
+        let (width, height) = (40, 30);
+        let shifted = |pixels: Vec<PixelCieLab>| -> Vec<PixelCieLab> {
+            pixels
+                .into_iter()
+                .map(|p| PixelCieLab::new(p.x() + 0.25, p.y() - 0.25, p.color().clone()))
+                .collect()
+        };
+        let mut outside = two_tone_pixels(width, height);
+        outside[0] = PixelCieLab::new(-0.6, 0.0, outside[0].color().clone());
+
+        // Act
+        let mut solver =
+            KMeansSuperPixelSolver::new(shifted(two_tone_pixels(width, height)), 10, 12, 40, 30)
+                .unwrap();
+        solver.solve_tick();
+        let outside = KMeansSuperPixelSolver::new(outside, 10, 12, 40, 30);
+
+        // Assert
+        for (centroid, cluster) in solver.clusters().iter().filter(|(_, c)| !c.is_empty()) {
+            let count = cluster.len() as f32;
+            let mean_x = cluster.iter().map(|p| p.pixel().x()).sum::<f32>() / count;
+            assert!((centroid.x() - mean_x).abs() < 1e-3);
+        }
+        assert_eq!(
+            (0.25, -0.25),
+            (
+                solver.pixels()[0].pixel().x(),
+                solver.pixels()[0].pixel().y()
+            )
+        );
+        assert!(matches!(outside, Err(Error::InvalidParameters(_))));
+    }
+}
@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+/// Milestones reported while a solver runs
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ProgressEvent {
+    /// seeds were placed and every pixel was assigned to its first superpixel
+    Seeded { superpixel_count: usize },
+    /// a tick of [`super::KMeansSuperPixelSolver::solve_until`] finished
+    Iteration {
+        /// zero based number of the tick
+        index: usize,
+        /// RMS distance the centroids moved during the tick
+        residual: f32,
+        /// time since the run started
+        elapsed: Duration,
+    },
+    /// fragments were merged into their neighbors after solving
+    PostProcessing {
+        superpixel_count_before: usize,
+        superpixel_count_after: usize,
+    },
+}
+
+/// Receives the [`ProgressEvent`]s of a solver.
+/// Closures taking a `&ProgressEvent` are observers as well.
+pub trait ProgressObserver: Send + Sync {
+    fn on_progress(&self, event: &ProgressEvent);
+}
+
+impl<F> ProgressObserver for F
+where
+    F: Fn(&ProgressEvent) + Send + Sync,
+{
+    fn on_progress(&self, event: &ProgressEvent) {
+        self(event)
+    }
+}
@@ -1,81 +1,242 @@
-mod colors;
-mod k_means_solver;
-mod pixels;
-
-use crate::colors::{ColorCieLab, ColorRgb, ColorXyz};
-use crate::k_means_solver::KMeansSuperPixelSolver;
-use crate::pixels::PixelCieLab;
-use image::{DynamicImage, GenericImage, GenericImageView, Rgb, RgbImage};
-use rand::Rng;
-
-fn pixel_from_rgb(x: u32, y: u32, rgb: &Rgb<u8>) -> PixelCieLab {
-    let c_rgb = ColorRgb::new(rgb.0[0], rgb.0[1], rgb.0[2]);
-    let xyz = ColorXyz::new_from_rgb(&c_rgb);
-    let cie = ColorCieLab::new_from_xyz(&xyz);
-
-    PixelCieLab::new(x, y, cie)
+//! Clusters images into superpixels using SLIC, a k-means variant working on
+//! CIE Lab colors and pixel positions.
+//!
+//! ```no_run
+//! use image_clustering::{segment, SuperpixelParams};
+//!
+//! let image = image::open("photo.jpg").unwrap();
+//! let segmentation = segment(&image, &SuperpixelParams::default()).unwrap();
+//! segmentation.to_rgb_image().save("photo_clustered.jpg").unwrap();
+//! ```
+
+pub mod colors;
+mod connectivity;
+mod error;
+mod input;
+pub mod k_means_solver;
+mod mask;
+pub mod pixels;
+pub mod seeding;
+mod segmentation;
+
+use image::DynamicImage;
+use std::path::{Path, PathBuf};
+
+pub use crate::colors::{
+    AdaptationMethod, ClusterColor, Color, ColorCieLab, ColorCieLch, ColorCieLuv, ColorDifference,
+    ColorHsl, ColorHsv, ColorLinearRgb, ColorOkLab, ColorOkLch, ColorRgb, ColorXyz, ColorYCbCr,
+    GamutMapping, WhitePoint, YCbCrStandard,
+};
+pub use crate::error::{Error, Result};
+pub use crate::input::{pixels_from_image, pixels_from_image_as};
+pub use crate::k_means_solver::{
+    CancellationToken, Compactness, KMeansSuperPixelSolver, ProgressEvent, ProgressObserver,
+    SolveReport, SolverBuilder, StopReason,
+};
+pub use crate::mask::Mask;
+pub use crate::pixels::{DistanceModel, Pixel};
+pub use crate::seeding::SeedLayout;
+pub use crate::segmentation::{BoundingBox, Region, Segmentation, NO_LABEL};
+
+/// Parameters of [`segment`]
+#[derive(Debug, Clone)]
+pub struct SuperpixelParams {
+    /// number of superpixels to cluster the image into
+    pub superpixel_count: usize,
+    /// how color distance is weighed against spatial distance
+    pub compactness: Compactness,
+    /// how color and spatial distance are combined
+    pub distance_model: DistanceModel,
+    /// perceptual color difference colors are compared by, `None` uses the euclidean distance in CIE Lab
+    pub color_difference: Option<ColorDifference>,
+    /// solving stops once the centroids move less than this
+    pub residual_threshold: f32,
+    /// solving stops after this many iterations, even if not converged
+    pub max_iterations: usize,
+    /// odd size of the neighborhood seeds are moved to the lowest gradient in, `None` keeps them on the grid
+    pub seed_neighborhood: Option<u32>,
+    /// fragments smaller than this fraction of a superpixel are merged into their neighbors,
+    /// `None` skips connectivity enforcement
+    pub min_size_fraction: Option<f32>,
+    /// fully transparent pixels are left out of clustering and labeled [`NO_LABEL`]
+    pub alpha_mask: bool,
+    /// only pixels inside the mask are clustered, the others are labeled [`NO_LABEL`]
+    pub mask: Option<Mask>,
 }
 
-fn test_generate_pixels(image: DynamicImage) -> RgbImage {
-    // convert to nice pixels
-    let rgb = image.as_rgb8().unwrap();
-    let pixels: Vec<PixelCieLab> = rgb
-        .enumerate_pixels()
-        .into_iter()
-        .map(|(x, y, rgb)| pixel_from_rgb(x, y, rgb))
-        .collect();
-
-    let superpixel_count = 600;
-
-    let mut solver = KMeansSuperPixelSolver::new(
-        pixels,
-        10,
-        superpixel_count,
+impl Default for SuperpixelParams {
+    fn default() -> Self {
+        SuperpixelParams {
+            superpixel_count: 600,
+            compactness: Compactness::Fixed(10),
+            distance_model: DistanceModel::default(),
+            color_difference: None,
+            residual_threshold: k_means_solver::DEFAULT_RESIDUAL_THRESHOLD,
+            max_iterations: k_means_solver::DEFAULT_MAX_ITERATIONS,
+            seed_neighborhood: Some(k_means_solver::DEFAULT_SEED_NEIGHBORHOOD),
+            min_size_fraction: Some(k_means_solver::DEFAULT_MIN_SIZE_FRACTION),
+            alpha_mask: false,
+            mask: None,
+        }
+    }
+}
+
+/// Clusters an image into superpixels
+pub fn segment(image: &DynamicImage, params: &SuperpixelParams) -> Result<Segmentation> {
+    let mut builder = SolverBuilder::new(
+        pixels_from_image(image)?,
         image.width() as usize,
         image.height() as usize,
-    );
+    )
+    .superpixel_count(params.superpixel_count)
+    .compactness(params.compactness)
+    .distance_model(params.distance_model.clone())
+    .residual_threshold(params.residual_threshold)
+    .max_iterations(params.max_iterations)
+    .seed_neighborhood(params.seed_neighborhood)
+    .connectivity(params.min_size_fraction);
+    if let Some(difference) = params.color_difference {
+        builder = builder.color_difference(difference);
+    }
+    // checked before the opacity mask of the image narrows it down
+    if let Some(mask) = &params.mask {
+        mask.check_size(image.width() as usize, image.height() as usize)?;
+    }
+    let opacity = match params.alpha_mask {
+        true => Mask::from_opacity(image),
+        false => None,
+    };
+    let mask = match (params.mask.as_ref(), opacity) {
+        (Some(mask), Some(opacity)) => Some(mask.intersect(&opacity)?),
+        (mask, opacity) => opacity.or_else(|| mask.cloned()),
+    };
+    if let Some(mask) = mask {
+        builder = builder.mask(mask);
+    }
 
-    // // TIcks scrample ordering of pixels around somehow
-    // for _ in 0..10 {
-    //     solver.solve_tick();
-    // }
+    let mut solver = builder.build()?;
+    solver.solve();
 
-    let superpixels = solver.current_superpixels();
+    Ok(solver.segmentation())
+}
 
-    let mut rng = rand::thread_rng();
-    let mut img = RgbImage::new(image.width(), image.height());
+fn clustered_file_path(path: &Path) -> Result<PathBuf> {
+    let stem = path
+        .file_stem()
+        .ok_or_else(|| Error::InvalidParameters(format!("{:?} is not a file", path)))?;
+    let mut file_name = stem.to_os_string();
+    file_name.push("_clustered.jpg");
 
-    for i in 0..superpixel_count {
-        // let r = rng.gen_range(0, 256) as u8;
-        // let g = rng.gen_range(0, 256) as u8;
-        // let b = rng.gen_range(0, 256) as u8;
-        // let rgb = Rgb([r, g, b]);
-        // // let rgb = Rgb([0, 0, 0]);
+    Ok(path.with_file_name(file_name))
+}
 
-        let rgb = superpixels[i][0].2;
+/// Clusters the image at `path` into superpixels and writes the result next to it,
+/// with `_clustered.jpg` appended to the file name
+pub fn generate_super_pixels(path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    let img = image::open(path).map_err(Error::Decode)?;
+    let result = segment(&img, &SuperpixelParams::default())?.to_rgb_image();
 
-        for pixel in &superpixels[i] {
-            img.put_pixel(pixel.0, pixel.1, rgb)
-        }
+    result
+        .save(clustered_file_path(path)?)
+        .map_err(Error::Encode)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{clustered_file_path, segment, Error, Mask, SuperpixelParams, NO_LABEL};
+    use image::{DynamicImage, GrayImage, Luma, Rgb, RgbImage, Rgba, RgbaImage};
+    use std::path::Path;
+
+    #[test]
+    fn clustered_file_path_should_keep_directories() {
+        // Act
+        let actual = clustered_file_path(Path::new("./images/photo.v2.png")).unwrap();
+
+        // Assert
+        assert_eq!(Path::new("./images/photo.v2_clustered.jpg"), actual);
     }
 
-    img
-}
+    #[test]
+    fn grayscale_images_should_be_segmented() {
+        // Arrange
+        let image =
+            DynamicImage::ImageLuma8(GrayImage::from_fn(40, 30, |x, _| Luma([x as u8 * 6])));
+        let params = SuperpixelParams {
+            superpixel_count: 12,
+            ..SuperpixelParams::default()
+        };
+
+        // Act
+        let segmentation = segment(&image, &params).unwrap();
+
+        // Assert
+        assert!(segmentation.superpixel_count() > 1);
+    }
 
-fn clustered_file_path(path: &String) -> String {
-    let start = path.split(&String::from(".")).next().unwrap();
-    let mut result = String::from(start);
-    result.push_str("_clustered.jpg");
+    #[test]
+    fn transparent_pixels_should_not_be_labeled() {
+        // Arrange
+        let image = DynamicImage::ImageRgba8(RgbaImage::from_fn(40, 30, |x, _| match x < 10 {
+            true => Rgba([0, 0, 0, 0]),
+            false => Rgba([30, 200, 30, 255]),
+        }));
+        let params = SuperpixelParams {
+            superpixel_count: 12,
+            alpha_mask: true,
+            ..SuperpixelParams::default()
+        };
+
+        // Act
+        let segmentation = segment(&image, &params).unwrap();
+
+        // Assert
+        assert_eq!(NO_LABEL, segmentation.label(0, 0));
+        assert_eq!(NO_LABEL, segmentation.label(9, 29));
+        assert!(segmentation.label(10, 0) < segmentation.superpixel_count() as u32);
+        let labeled: usize = segmentation.regions().map(|r| r.pixel_count()).sum();
+        assert_eq!(30 * 30, labeled);
+    }
 
-    result
-}
+    #[test]
+    fn masks_of_another_size_should_error_with_the_alpha_mask() {
+        // Arrange
+        let image =
+            DynamicImage::ImageRgba8(RgbaImage::from_pixel(10, 10, Rgba([30, 200, 30, 255])));
+        let params = SuperpixelParams {
+            superpixel_count: 4,
+            alpha_mask: true,
+            mask: Some(Mask::from_fn(50, 50, |_, _| true)),
+            ..SuperpixelParams::default()
+        };
+
+        // Act
+        let result = segment(&image, &params);
+
+        // Assert
+        assert!(matches!(result, Err(Error::InvalidParameters(_))));
+    }
 
-pub fn generate_super_pixels(path: String) {
-    match image::open(path.clone()) {
-        Ok(img) => {
-            let result = test_generate_pixels(img);
-            result.save(clustered_file_path(&path));
-        }
-        Err(e) => println!("Error: {:?}", e),
-    };
+    #[test]
+    fn segment_should_label_every_pixel() {
+        // Arrange
+        let image = DynamicImage::ImageRgb8(RgbImage::from_fn(64, 48, |x, _| match x < 32 {
+            true => Rgb([200, 30, 30]),
+            false => Rgb([30, 30, 200]),
+        }));
+        let params = SuperpixelParams {
+            superpixel_count: 12,
+            ..SuperpixelParams::default()
+        };
+
+        // Act
+        let segmentation = segment(&image, &params).unwrap();
+
+        // Assert
+        assert_eq!(64 * 48, segmentation.labels().len());
+        assert!(segmentation
+            .labels()
+            .iter()
+            .all(|l| (*l as usize) < segmentation.superpixel_count()));
+    }
 }
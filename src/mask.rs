@@ -0,0 +1,189 @@
+use crate::error::{Error, Result};
+use image::{DynamicImage, GrayImage};
+
+/// Pixels of an image that take part in clustering, the others are labeled
+/// [`crate::NO_LABEL`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask {
+    width: u32,
+    height: u32,
+    /// true for pixels inside the mask, in row-major order
+    values: Vec<bool>,
+}
+
+impl Mask {
+    /// Pixels with a non-zero value are inside the mask
+    pub fn from_image(image: &GrayImage) -> Mask {
+        Mask {
+            width: image.width(),
+            height: image.height(),
+            values: image.pixels().map(|p| p[0] > 0).collect(),
+        }
+    }
+
+    /// Pixels at positions `inside(x, y)` returns true for are inside the mask
+    pub fn from_fn(width: u32, height: u32, inside: impl Fn(u32, u32) -> bool) -> Mask {
+        let values = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| inside(x, y))
+            .collect();
+
+        Mask {
+            width,
+            height,
+            values,
+        }
+    }
+
+    /// Pixels that are not fully transparent are inside the mask.
+    /// `None` if the image has no alpha channel.
+    pub fn from_opacity(image: &DynamicImage) -> Option<Mask> {
+        let values: Vec<bool> = match image {
+            DynamicImage::ImageLumaA8(i) => i.pixels().map(|p| p[1] > 0).collect(),
+            DynamicImage::ImageRgba8(i) => i.pixels().map(|p| p[3] > 0).collect(),
+            DynamicImage::ImageLumaA16(i) => i.pixels().map(|p| p[1] > 0).collect(),
+            DynamicImage::ImageRgba16(i) => i.pixels().map(|p| p[3] > 0).collect(),
+            DynamicImage::ImageRgba32F(i) => i.pixels().map(|p| p[3] > 0.0).collect(),
+            _ => return None,
+        };
+
+        Some(Mask {
+            width: image.width(),
+            height: image.height(),
+            values,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// true if the pixel at (x, y) is inside the mask. Panics if (x, y) is outside of it.
+    pub fn contains(&self, x: u32, y: u32) -> bool {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is outside of the {}x{} mask",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+
+        self.values[y as usize * self.width as usize + x as usize]
+    }
+
+    /// Errors unless the mask has the size of a `width` x `height` image
+    pub(crate) fn check_size(&self, width: usize, height: usize) -> Result<()> {
+        match (self.width as usize, self.height as usize) == (width, height) {
+            true => Ok(()),
+            false => Err(Error::InvalidParameters(format!(
+                "a {}x{} mask doesn't fit a {}x{} image",
+                self.width, self.height, width, height
+            ))),
+        }
+    }
+
+    /// Number of pixels inside the mask
+    pub fn count(&self) -> usize {
+        self.values.iter().filter(|v| **v).count()
+    }
+
+    /// Pixels inside both masks, which need to have the same size
+    pub fn intersect(&self, other: &Mask) -> Result<Mask> {
+        other.check_size(self.width as usize, self.height as usize)?;
+
+        Ok(Mask {
+            width: self.width,
+            height: self.height,
+            values: self
+                .values
+                .iter()
+                .zip(&other.values)
+                .map(|(a, b)| *a && *b)
+                .collect(),
+        })
+    }
+
+    /// true for pixels inside the mask, in row-major order
+    pub fn values(&self) -> &[bool] {
+        &self.values
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::Error;
+    use crate::mask::Mask;
+    use image::{DynamicImage, GrayImage, Luma, RgbImage, Rgba, RgbaImage};
+
+    #[test]
+    fn masks_should_agree_on_the_same_area() {
+        // Arrange
+        let image = GrayImage::from_fn(4, 3, |x, _| Luma([x as u8 * 80]));
+
+        // Act
+        let from_image = Mask::from_image(&image);
+        let from_fn = Mask::from_fn(4, 3, |x, _| x > 0);
+
+        // Assert
+        assert_eq!(from_image, from_fn);
+        assert_eq!(9, from_fn.count());
+        assert!(!from_fn.contains(0, 2));
+        assert!(from_fn.contains(3, 2));
+    }
+
+    #[test]
+    fn transparent_pixels_should_be_outside() {
+        // Arrange
+        let image = RgbaImage::from_fn(3, 1, |x, _| Rgba([10, 20, 30, x as u8 * 100]));
+
+        // Act
+        let mask = Mask::from_opacity(&DynamicImage::ImageRgba8(image)).unwrap();
+
+        // Assert
+        assert_eq!(&[false, true, true], mask.values());
+        assert_eq!(
+            None,
+            Mask::from_opacity(&DynamicImage::ImageRgb8(RgbImage::new(2, 2)))
+        );
+    }
+
+    #[test]
+    fn intersection_should_keep_common_pixels() {
+        // Arrange
+        let left = Mask::from_fn(4, 1, |x, _| x < 3);
+        let right = Mask::from_fn(4, 1, |x, _| x > 0);
+
+        // Act
+        let actual = left.intersect(&right).unwrap();
+
+        // Assert
+        assert_eq!(&[false, true, true, false], actual.values());
+    }
+
+    #[test]
+    fn intersection_of_different_sizes_should_error() {
+        // Arrange
+        let small = Mask::from_fn(4, 1, |_, _| true);
+        let large = Mask::from_fn(4, 2, |_, _| true);
+
+        // Act
+        let result = small.intersect(&large);
+
+        // Assert
+        assert!(matches!(result, Err(Error::InvalidParameters(_))));
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of the 4x1 mask")]
+    fn positions_outside_the_mask_should_panic() {
+        // Arrange
+        let mask = Mask::from_fn(4, 1, |_, _| true);
+
+        // Act, (4, 0) would wrap to the next row
+        mask.contains(4, 0);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::colors::{ClusterColor, ColorCieLab};
+use crate::pixels::Pixel;
+use cgmath::{MetricSpace, Point2};
+
+/// Center of a superpixel, a free floating color and position
+#[derive(Debug, Clone, PartialEq)]
+pub struct Centroid<C = ColorCieLab> {
+    color: C,
+    position: Point2<f32>,
+}
+
+impl<C: ClusterColor> Centroid<C> {
+    pub fn x(&self) -> f32 {
+        self.position.x
+    }
+    pub fn y(&self) -> f32 {
+        self.position.y
+    }
+    pub fn color(&self) -> &C {
+        &self.color
+    }
+    pub fn position(&self) -> Point2<f32> {
+        self.position
+    }
+
+    pub const fn new_from_color(color: C, x: f32, y: f32) -> Centroid<C> {
+        Centroid {
+            color,
+            position: Point2::new(x, y),
+        }
+    }
+
+    pub fn new_from_pixel(pixel: &Pixel<C>) -> Centroid<C> {
+        Centroid::new_from_color(pixel.color().clone(), pixel.x(), pixel.y())
+    }
+
+    /// Squared euclidean distance in the combined color and position space
+    pub fn distance2(&self, other: &Centroid<C>) -> f32 {
+        self.color.distance2(&other.color) + self.position.distance2(other.position)
+    }
+}
+
+impl Centroid<ColorCieLab> {
+    pub fn l(&self) -> f32 {
+        self.color.l()
+    }
+    pub fn a(&self) -> f32 {
+        self.color.a()
+    }
+    pub fn b(&self) -> f32 {
+        self.color.b()
+    }
+    /// `[l, a, b, x, y]` vector of the centroid
+    pub fn values(&self) -> [f32; 5] {
+        [self.l(), self.a(), self.b(), self.x(), self.y()]
+    }
+
+    pub const fn new(l: f32, a: f32, b: f32, x: f32, y: f32) -> Centroid {
+        Centroid::new_from_color(ColorCieLab::new(l, a, b), x, y)
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+use std::sync::Arc;
+
+/// Signature of a custom distance model. It receives the squared color distance,
+/// the squared spatial distance, the compactness m and the superpixel size S.
+pub type DistanceFn = dyn Fn(f32, f32, f32, f32) -> f32 + Send + Sync;
+
+/// Combines color and spatial distance into the distance pixels are clustered by
+#[derive(Clone, Default)]
+pub enum DistanceModel {
+    /// `D = sqrt(dc² + (ds / S)² m²)`, as defined in the SLIC paper
+    #[default]
+    Slic,
+    /// `dc² + (m / S) ds²`, as computed by earlier versions of this crate
+    Legacy,
+    /// a user supplied combination, see [`DistanceFn`]
+    Custom(Arc<DistanceFn>),
+}
+
+impl DistanceModel {
+    pub fn custom(f: impl Fn(f32, f32, f32, f32) -> f32 + Send + Sync + 'static) -> Self {
+        DistanceModel::Custom(Arc::new(f))
+    }
+
+    pub fn combine(&self, color_distance2: f32, spatial_distance2: f32, m: f32, s: f32) -> f32 {
+        match self {
+            DistanceModel::Slic => (color_distance2 + spatial_distance2 / (s * s) * m * m).sqrt(),
+            DistanceModel::Legacy => color_distance2 + (m / s) * spatial_distance2,
+            DistanceModel::Custom(f) => f(color_distance2, spatial_distance2, m, s),
+        }
+    }
+}
+
+impl fmt::Debug for DistanceModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DistanceModel::Slic => write!(f, "Slic"),
+            DistanceModel::Legacy => write!(f, "Legacy"),
+            DistanceModel::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::pixels::DistanceModel;
+
+    #[test]
+    fn slic_should_match_paper_formula() {
+        // Arrange
+        let (dc, ds, m, s) = (3.0_f32, 20.0_f32, 10.0, 40.0);
+
+        // Act
+        let actual = DistanceModel::Slic.combine(dc * dc, ds * ds, m, s);
+
+        // Assert
+        let expected = (dc.powi(2) + (ds / s).powi(2) * m.powi(2)).sqrt();
+        assert!((expected - actual).abs() < 1e-5);
+    }
+
+    #[test]
+    fn legacy_should_reproduce_previous_distance() {
+        // Act
+        let actual = DistanceModel::Legacy.combine(9.0, 400.0, 10.0, 40.0);
+
+        // Assert
+        assert_eq!(9.0 + 0.25 * 400.0, actual);
+    }
+
+    #[test]
+    fn custom_should_call_closure() {
+        // Arrange
+        let model = DistanceModel::custom(|dc2, ds2, _, _| dc2.max(ds2));
+
+        // Act
+        let actual = model.combine(9.0, 400.0, 10.0, 40.0);
+
+        // Assert
+        assert_eq!(400.0, actual);
+    }
+}
@@ -1,57 +1,61 @@
 use cgmath::Point2;
-use num::clamp;
-
-const MIN_WIDTH: u8 = 0;
-const MIN_HEIGHT: u8 = 0;
+use std::ops::RangeInclusive;
 
+/// A square window of pixel positions around a center, clamped to the image bounds.
 pub struct Grid {
-    points: Vec<Point2<u16>>,
+    min: Point2<u32>,
+    max: Point2<u32>,
 }
 
 impl Grid {
+    /// Creates a grid reaching `ring_count` pixels from `center` in every direction.
+    /// `max_width` and `max_height` are the image dimensions, so the last valid
+    /// position is `(max_width - 1, max_height - 1)`.
     pub fn new(
-        center: impl Into<Point2<u16>>,
-        ring_count: u16,
-        max_width: u16,
-        max_height: u16,
+        center: impl Into<Point2<u32>>,
+        ring_count: u32,
+        max_width: u32,
+        max_height: u32,
     ) -> Grid {
-        let center_point: Point2<u16> = center.into();
-
-        let min_x: u16 = num::clamp(
-            center_point.x as i16 - ring_count as i16,
-            MIN_WIDTH as i16,
-            max_width as i16,
-        ) as u16;
-        let min_y: u16 = num::clamp(
-            center_point.y as i16 - ring_count as i16,
-            MIN_HEIGHT as i16,
-            max_height as i16,
-        ) as u16;
+        let center_point: Point2<u32> = center.into();
+        let last_x = max_width.saturating_sub(1);
+        let last_y = max_height.saturating_sub(1);
 
-        let max_x: u16 = num::clamp(
-            center_point.x as u16 + ring_count as u16,
-            MIN_WIDTH as u16,
-            max_width,
+        let min = Point2::new(
+            center_point.x.saturating_sub(ring_count).min(last_x),
+            center_point.y.saturating_sub(ring_count).min(last_y),
         );
-        let max_y: u16 = num::clamp(
-            center_point.y as u16 + ring_count as u16,
-            MIN_HEIGHT as u16,
-            max_height,
+        let max = Point2::new(
+            center_point.x.saturating_add(ring_count).min(last_x),
+            center_point.y.saturating_add(ring_count).min(last_y),
         );
 
-        let mut points: Vec<Point2<u16>> = Vec::with_capacity(max_x as usize * max_y as usize);
+        Grid { min, max }
+    }
 
-        for x in min_x..max_x {
-            for y in min_y..max_y {
-                points.push(Point2::new(x, y));
-            }
-        }
+    pub fn min(&self) -> Point2<u32> {
+        self.min
+    }
+    pub fn max(&self) -> Point2<u32> {
+        self.max
+    }
+
+    pub fn x_range(&self) -> RangeInclusive<u32> {
+        self.min.x..=self.max.x
+    }
+    pub fn y_range(&self) -> RangeInclusive<u32> {
+        self.min.y..=self.max.y
+    }
 
-        Grid { points }
+    pub fn contains(&self, position: impl Into<Point2<u32>>) -> bool {
+        let point: Point2<u32> = position.into();
+        self.x_range().contains(&point.x) && self.y_range().contains(&point.y)
     }
 
-    pub fn points(&self) -> &Vec<Point2<u16>> {
-        &self.points
+    pub fn points(&self) -> impl Iterator<Item = Point2<u32>> {
+        let x_range = self.x_range();
+        self.y_range()
+            .flat_map(move |y| x_range.clone().map(move |x| Point2::new(x, y)))
     }
 }
 
@@ -64,7 +68,26 @@ mod test {
         // Arrange
         let grid = Grid::new((19, 0), 4, 20, 20);
 
-        println!("{:?}", grid.points());
-        assert_eq!(6, grid.points().len())
+        // Assert
+        assert_eq!(25, grid.points().count())
+    }
+
+    #[test]
+    fn grids_should_cover_every_pixel() {
+        // Arrange
+        let (width, height, ring_count) = (23, 17, 3);
+        let mut covered = vec![false; (width * height) as usize];
+
+        // Act
+        for x in (0..width).step_by(ring_count as usize) {
+            for y in (0..height).step_by(ring_count as usize) {
+                for point in Grid::new((x, y), ring_count, width, height).points() {
+                    covered[(point.y * width + point.x) as usize] = true;
+                }
+            }
+        }
+
+        // Assert
+        assert!(covered.into_iter().all(|c| c));
     }
 }
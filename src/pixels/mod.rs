@@ -1,11 +1,14 @@
-pub use self::pixel::Pixel;
+pub use self::pixel::{Pixel, PixelCieLab};
 mod pixel;
 
-pub use self::pixel_cie_lab::PixelCieLab;
-mod pixel_cie_lab;
-
 pub use self::grid::Grid;
 mod grid;
 
 pub use self::rect::Rectangle;
 mod rect;
+
+pub use self::centroid::Centroid;
+mod centroid;
+
+pub use self::distance_model::{DistanceFn, DistanceModel};
+mod distance_model;
@@ -1,28 +1,66 @@
-use crate::colors::{Color, ColorCieLab};
-use cgmath::Vector2;
+use crate::colors::{ClusterColor, Color, ColorCieLab};
+use cgmath::Point2;
 
-pub struct Pixel {
-    values: Vector2<f32>,
-    color: Color,
+/// A color sample at a sub-pixel accurate position. Pixel centers lie on whole
+/// coordinates, so the pixel in column x and row y sits at (x, y).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pixel<C = Color> {
+    values: Point2<f32>,
+    color: C,
 }
 
-impl Pixel {
+/// A pixel clustered in CIE Lab
+pub type PixelCieLab = Pixel<ColorCieLab>;
+
+impl<C> Pixel<C> {
     pub fn x(&self) -> f32 {
-        self.values[0]
+        self.values.x
     }
     pub fn y(&self) -> f32 {
-        self.values[1]
+        self.values.y
     }
-    pub fn color(&self) -> &Color {
+    pub fn color(&self) -> &C {
         &self.color
     }
-    pub fn values(&self) -> &Vector2<f32> {
+    pub fn values(&self) -> &Point2<f32> {
         &self.values
     }
 
-    pub fn new(x: f32, y: f32, color: Color) -> Pixel {
-        let values = Vector2::new(x, y);
+    pub const fn new(x: f32, y: f32, color: C) -> Pixel<C> {
+        let values = Point2::new(x, y);
 
         Pixel { values, color }
     }
 }
+
+impl Pixel<Color> {
+    /// The same sample, with its color converted to the color space `C`
+    pub fn to_color_space<C: ClusterColor>(&self) -> Pixel<C> {
+        Pixel::new(self.x(), self.y(), C::from_xyz(&self.color.to_xyz()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::{ClusterColor, Color, ColorCieLab, ColorOkLab, ColorRgb};
+    use crate::pixels::Pixel;
+
+    #[test]
+    fn mixed_colors_should_convert_to_one_color_space() {
+        // Arrange
+        let rgb = ColorRgb::new(40, 160, 220);
+        let lab = ColorCieLab::from_rgb(&rgb);
+        let pixels = [
+            Pixel::new(0.25, 0.0, Color::from(rgb.clone())),
+            Pixel::new(1.0, 0.5, Color::from(lab)),
+        ];
+
+        // Act
+        let converted: Vec<Pixel<ColorOkLab>> = pixels.iter().map(|p| p.to_color_space()).collect();
+
+        // Assert
+        assert_eq!((0.25, 0.0), (converted[0].x(), converted[0].y()));
+        assert_eq!(rgb, converted[0].color().to_rgb());
+        assert_eq!(rgb, converted[1].color().to_rgb());
+    }
+}
@@ -0,0 +1,302 @@
+use crate::pixels::Rectangle;
+use cgmath::Point2;
+use rand::seq::{index, SliceRandom};
+use rand::Rng;
+
+/// Candidates tried around every active sample before it is retired, see Bridson (2007)
+const POISSON_DISK_CANDIDATES: usize = 30;
+/// How often the poisson disk radius is shrunk before falling back to a jittered grid
+const POISSON_DISK_ATTEMPTS: usize = 8;
+/// Factor the poisson disk radius is shrunk by if too few samples fit
+const POISSON_DISK_SHRINK: f32 = 0.8;
+
+/// How initial superpixel seeds are spread over the image
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SeedLayout {
+    /// regular square grid
+    #[default]
+    Square,
+    /// every other row is shifted by half a cell, so neighbors are equally far apart
+    Hexagonal,
+    /// one random position inside every cell of a square grid
+    Jittered,
+    /// random positions that keep a minimum distance to each other
+    PoissonDisk,
+    /// uniformly random positions
+    Random,
+}
+
+impl SeedLayout {
+    /// Samples exactly `n` positions inside `rect`. Positions only repeat if `n`
+    /// exceeds the number of pixels, an empty rectangle yields no positions.
+    pub fn sample<R: Rng + ?Sized>(
+        &self,
+        rect: &Rectangle,
+        n: u32,
+        rng: &mut R,
+    ) -> Vec<Point2<u32>> {
+        if n == 0 || rect.width() == 0 || rect.height() == 0 {
+            return Vec::new();
+        }
+
+        match self {
+            SeedLayout::Square => square_grid(rect, n),
+            SeedLayout::Hexagonal => hexagonal_grid(rect, n),
+            SeedLayout::Jittered => jittered_grid(rect, n, rng),
+            SeedLayout::PoissonDisk => poisson_disk(rect, n, rng),
+            SeedLayout::Random => uniform_random(rect, n, rng),
+        }
+    }
+}
+
+/// Rows of a grid holding exactly `n` cells. `row_factor` is the height of a row
+/// relative to the width of a cell. Every row holds `n / rows` or one more cells.
+fn grid_rows(rect: &Rectangle, n: u32, row_factor: f32) -> Vec<u32> {
+    let (width, height) = (rect.width() as f32, rect.height() as f32);
+    let columns = (n as f32 * width / (height * row_factor))
+        .sqrt()
+        .round()
+        .max(1.0)
+        .min(n as f32) as u32;
+    let rows = n.div_ceil(columns);
+
+    (0..rows)
+        .map(|row| n / rows + if row < n % rows { 1 } else { 0 })
+        .collect()
+}
+
+/// Converts a position in continuous image space to the pixel containing it
+fn to_pixel(rect: &Rectangle, x: f32, y: f32) -> Point2<u32> {
+    Point2::new(
+        (x.max(0.0) as u32).min(rect.width() - 1),
+        (y.max(0.0) as u32).min(rect.height() - 1),
+    )
+}
+
+/// Places cells of every row at `offset` (between 0 and 1) of their width
+fn layout_rows(
+    rect: &Rectangle,
+    rows: &[u32],
+    mut offset: impl FnMut(usize, u32) -> (f32, f32),
+) -> Vec<Point2<u32>> {
+    let cell_height = rect.height() as f32 / rows.len() as f32;
+    let mut positions = Vec::with_capacity(rows.iter().sum::<u32>() as usize);
+
+    for (row, count) in rows.iter().enumerate() {
+        let cell_width = rect.width() as f32 / *count as f32;
+
+        for column in 0..*count {
+            let (offset_x, offset_y) = offset(row, column);
+            let x = (column as f32 + offset_x) * cell_width;
+            let y = (row as f32 + offset_y) * cell_height;
+            positions.push(to_pixel(rect, x, y));
+        }
+    }
+
+    positions
+}
+
+pub fn square_grid(rect: &Rectangle, n: u32) -> Vec<Point2<u32>> {
+    let rows = grid_rows(rect, n, 1.0);
+    layout_rows(rect, &rows, |_, _| (0.5, 0.5))
+}
+
+pub fn hexagonal_grid(rect: &Rectangle, n: u32) -> Vec<Point2<u32>> {
+    let rows = grid_rows(rect, n, 3.0_f32.sqrt() / 2.0);
+    layout_rows(rect, &rows, |row, _| match row % 2 {
+        0 => (0.25, 0.5),
+        _ => (0.75, 0.5),
+    })
+}
+
+pub fn jittered_grid<R: Rng + ?Sized>(rect: &Rectangle, n: u32, rng: &mut R) -> Vec<Point2<u32>> {
+    let rows = grid_rows(rect, n, 1.0);
+    layout_rows(rect, &rows, |_, _| (rng.gen::<f32>(), rng.gen::<f32>()))
+}
+
+/// Distinct random pixels, positions only repeat once every pixel was drawn
+pub fn uniform_random<R: Rng + ?Sized>(rect: &Rectangle, n: u32, rng: &mut R) -> Vec<Point2<u32>> {
+    let pixel_count = rect.width() as usize * rect.height() as usize;
+
+    index::sample(rng, pixel_count, (n as usize).min(pixel_count))
+        .into_iter()
+        .cycle()
+        .take(n as usize)
+        .map(|i| {
+            Point2::new(
+                (i % rect.width() as usize) as u32,
+                (i / rect.width() as usize) as u32,
+            )
+        })
+        .collect()
+}
+
+/// Poisson disk sampling after Bridson (2007). The radius starts at the spacing of
+/// a square grid and shrinks until at least `n` samples fit, which are then thinned
+/// out randomly to exactly `n`.
+pub fn poisson_disk<R: Rng + ?Sized>(rect: &Rectangle, n: u32, rng: &mut R) -> Vec<Point2<u32>> {
+    let mut radius = (rect.width() as f32 * rect.height() as f32 / n as f32).sqrt();
+
+    for _ in 0..POISSON_DISK_ATTEMPTS {
+        let mut samples = bridson(rect, radius, rng);
+
+        if samples.len() >= n as usize {
+            samples.shuffle(rng);
+            samples.truncate(n as usize);
+            return samples
+                .into_iter()
+                .map(|(x, y)| to_pixel(rect, x, y))
+                .collect();
+        }
+
+        radius *= POISSON_DISK_SHRINK;
+    }
+
+    // more samples than pixels, no minimum distance can be kept anyways
+    jittered_grid(rect, n, rng)
+}
+
+fn bridson<R: Rng + ?Sized>(rect: &Rectangle, radius: f32, rng: &mut R) -> Vec<(f32, f32)> {
+    let (width, height) = (rect.width() as f32, rect.height() as f32);
+    let cell_size = radius / 2.0_f32.sqrt();
+    let columns = (width / cell_size).ceil() as usize;
+    let rows = (height / cell_size).ceil() as usize;
+    let cell = |x: f32, y: f32| {
+        (
+            ((x / cell_size) as usize).min(columns - 1),
+            ((y / cell_size) as usize).min(rows - 1),
+        )
+    };
+
+    // every cell holds at most one sample, as its diagonal equals the radius
+    let mut cells: Vec<Option<usize>> = vec![None; columns * rows];
+    let mut samples = Vec::new();
+    let mut active = Vec::new();
+
+    let first = (rng.gen_range(0.0, width), rng.gen_range(0.0, height));
+    let (cx, cy) = cell(first.0, first.1);
+    cells[cy * columns + cx] = Some(0);
+    samples.push(first);
+    active.push(0);
+
+    while !active.is_empty() {
+        let active_index = rng.gen_range(0, active.len());
+        let (px, py) = samples[active[active_index]];
+        let mut found = false;
+
+        for _ in 0..POISSON_DISK_CANDIDATES {
+            let angle = rng.gen_range(0.0, std::f32::consts::PI * 2.0);
+            let distance = rng.gen_range(radius, radius * 2.0);
+            let (x, y) = (px + angle.cos() * distance, py + angle.sin() * distance);
+
+            if x < 0.0 || y < 0.0 || x >= width || y >= height {
+                continue;
+            }
+
+            let (cx, cy) = cell(x, y);
+            let too_close = (cy.saturating_sub(2)..(cy + 3).min(rows)).any(|ny| {
+                (cx.saturating_sub(2)..(cx + 3).min(columns)).any(|nx| {
+                    cells[ny * columns + nx].is_some_and(|i| {
+                        let (sx, sy) = samples[i];
+                        (sx - x).powi(2) + (sy - y).powi(2) < radius * radius
+                    })
+                })
+            });
+
+            if !too_close {
+                cells[cy * columns + cx] = Some(samples.len());
+                active.push(samples.len());
+                samples.push((x, y));
+                found = true;
+                break;
+            }
+        }
+
+        if !found {
+            active.swap_remove(active_index);
+        }
+    }
+
+    samples
+}
+
+#[cfg(test)]
+mod test {
+    use crate::pixels::Rectangle;
+    use crate::seeding::SeedLayout;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    const LAYOUTS: [SeedLayout; 5] = [
+        SeedLayout::Square,
+        SeedLayout::Hexagonal,
+        SeedLayout::Jittered,
+        SeedLayout::PoissonDisk,
+        SeedLayout::Random,
+    ];
+
+    #[test]
+    fn every_layout_should_sample_exactly_n_positions_in_bounds() {
+        // Arrange
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let cases = [
+            (20, 30, 10),
+            (640, 480, 600),
+            (1, 100, 7),
+            (3, 500, 50),
+            (500, 2, 13),
+            (5, 5, 40),
+            (1, 1, 1),
+            (7, 3, 1),
+        ];
+
+        for layout in LAYOUTS.iter() {
+            for (width, height, n) in cases.iter() {
+                // Act
+                let rect = Rectangle::new(*width, *height);
+                let positions = layout.sample(&rect, *n, &mut rng);
+
+                // Assert
+                assert_eq!(
+                    *n as usize,
+                    positions.len(),
+                    "{:?} {:?}",
+                    layout,
+                    (width, height)
+                );
+                assert!(positions.iter().all(|p| p.x < *width && p.y < *height));
+            }
+        }
+    }
+
+    #[test]
+    fn grid_layouts_should_not_repeat_positions() {
+        // Arrange
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let rect = Rectangle::new(200, 100);
+
+        for layout in LAYOUTS.iter() {
+            // Act
+            let mut positions = layout.sample(&rect, 150, &mut rng);
+            positions.sort_by_key(|p| (p.x, p.y));
+            positions.dedup();
+
+            // Assert
+            assert_eq!(150, positions.len(), "{:?}", layout);
+        }
+    }
+
+    #[test]
+    fn empty_rectangles_should_sample_nothing() {
+        // Arrange
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        for layout in LAYOUTS.iter() {
+            // Act
+            let positions = layout.sample(&Rectangle::new(0, 10), 5, &mut rng);
+
+            // Assert
+            assert!(positions.is_empty());
+        }
+    }
+}
@@ -0,0 +1,346 @@
+use crate::colors::{ColorCieLab, ColorRgb, GamutMapping};
+use cgmath::Point2;
+use image::{Rgb, RgbImage};
+
+/// Label of pixels that were left out of clustering
+pub const NO_LABEL: u32 = u32::MAX;
+
+/// Smallest axis aligned rectangle containing all pixels of a region, bounds are inclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingBox {
+    min: Point2<u32>,
+    max: Point2<u32>,
+}
+
+impl BoundingBox {
+    pub fn min(&self) -> Point2<u32> {
+        self.min
+    }
+    pub fn max(&self) -> Point2<u32> {
+        self.max
+    }
+    pub fn width(&self) -> u32 {
+        self.max.x - self.min.x + 1
+    }
+    pub fn height(&self) -> u32 {
+        self.max.y - self.min.y + 1
+    }
+
+    fn new(position: Point2<u32>) -> BoundingBox {
+        BoundingBox {
+            min: position,
+            max: position,
+        }
+    }
+
+    fn extend(&mut self, position: Point2<u32>) {
+        self.min = Point2::new(self.min.x.min(position.x), self.min.y.min(position.y));
+        self.max = Point2::new(self.max.x.max(position.x), self.max.y.max(position.y));
+    }
+}
+
+/// Statistics of a single superpixel
+#[derive(Debug, Clone)]
+pub struct Region {
+    label: u32,
+    mean_lab: ColorCieLab,
+    centroid: Point2<f32>,
+    pixel_count: usize,
+    bounding_box: BoundingBox,
+    perimeter: usize,
+}
+
+impl Region {
+    pub fn label(&self) -> u32 {
+        self.label
+    }
+    pub fn mean_lab(&self) -> &ColorCieLab {
+        &self.mean_lab
+    }
+    /// Mean color of the region, converted to sRGB by the default [`GamutMapping`]
+    pub fn mean_rgb(&self) -> ColorRgb {
+        self.mean_rgb_with(GamutMapping::default())
+    }
+    pub fn mean_rgb_with(&self, mapping: GamutMapping) -> ColorRgb {
+        self.mean_lab.as_xyz().as_rgb_with(mapping)
+    }
+    /// Mean position of the pixels in the region
+    pub fn centroid(&self) -> Point2<f32> {
+        self.centroid
+    }
+    pub fn pixel_count(&self) -> usize {
+        self.pixel_count
+    }
+    pub fn bounding_box(&self) -> BoundingBox {
+        self.bounding_box
+    }
+    /// Number of pixel edges separating the region from other regions or the image border
+    pub fn perimeter(&self) -> usize {
+        self.perimeter
+    }
+}
+
+/// Running sums of a region, while the label map is scanned
+struct RegionSums {
+    color: [f64; 3],
+    position: [f64; 2],
+    pixel_count: usize,
+    bounding_box: BoundingBox,
+    perimeter: usize,
+}
+
+/// Superpixels an image was clustered into
+#[derive(Debug, Clone)]
+pub struct Segmentation {
+    width: u32,
+    height: u32,
+    /// superpixel of every pixel, in row-major order
+    labels: Vec<u32>,
+    /// statistics of every superpixel, indexed by label
+    regions: Vec<Region>,
+}
+
+impl Segmentation {
+    /// Builds a segmentation from the label and color of every pixel, in row-major order.
+    /// Labels without pixels are dropped, so labels are renumbered to stay contiguous.
+    /// Pixels labeled `usize::MAX` were left out and get [`NO_LABEL`].
+    pub(crate) fn new(
+        width: u32,
+        height: u32,
+        pixels: impl Iterator<Item = (usize, ColorCieLab)>,
+    ) -> Segmentation {
+        let (raw_labels, colors): (Vec<usize>, Vec<ColorCieLab>) = pixels.unzip();
+        debug_assert_eq!(width as usize * height as usize, raw_labels.len());
+
+        // renumber labels in ascending order, skipping labels without pixels
+        let label_count = raw_labels
+            .iter()
+            .filter(|l| **l != usize::MAX)
+            .max()
+            .map_or(0, |l| l + 1);
+        let mut used = vec![false; label_count];
+        for label in raw_labels.iter().filter(|l| **l != usize::MAX) {
+            used[*label] = true;
+        }
+        let mut new_labels = vec![0; label_count];
+        let mut count = 0;
+        for (label, used) in used.into_iter().enumerate() {
+            if used {
+                new_labels[label] = count;
+                count += 1;
+            }
+        }
+        let labels: Vec<u32> = raw_labels
+            .iter()
+            .map(|l| match *l == usize::MAX {
+                true => NO_LABEL,
+                false => new_labels[*l],
+            })
+            .collect();
+
+        let mut sums: Vec<Option<RegionSums>> = (0..count).map(|_| None).collect();
+        for (index, (label, color)) in labels.iter().zip(colors).enumerate() {
+            if *label == NO_LABEL {
+                continue;
+            }
+
+            let position = Point2::new(
+                (index % width as usize) as u32,
+                (index / width as usize) as u32,
+            );
+            let sum = sums[*label as usize].get_or_insert_with(|| RegionSums {
+                color: [0.0; 3],
+                position: [0.0; 2],
+                pixel_count: 0,
+                bounding_box: BoundingBox::new(position),
+                perimeter: 0,
+            });
+
+            sum.color[0] += color.l() as f64;
+            sum.color[1] += color.a() as f64;
+            sum.color[2] += color.b() as f64;
+            sum.position[0] += position.x as f64;
+            sum.position[1] += position.y as f64;
+            sum.pixel_count += 1;
+            sum.bounding_box.extend(position);
+
+            let (x, y) = (position.x as i64, position.y as i64);
+            let neighbors = [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)];
+            sum.perimeter += neighbors
+                .iter()
+                .filter(|(nx, ny)| {
+                    *nx < 0
+                        || *ny < 0
+                        || *nx >= width as i64
+                        || *ny >= height as i64
+                        || labels[(*ny * width as i64 + *nx) as usize] != *label
+                })
+                .count();
+        }
+
+        let regions = sums
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .map(|(label, sum)| {
+                let count = sum.pixel_count as f64;
+                Region {
+                    label: label as u32,
+                    mean_lab: ColorCieLab::new(
+                        (sum.color[0] / count) as f32,
+                        (sum.color[1] / count) as f32,
+                        (sum.color[2] / count) as f32,
+                    ),
+                    centroid: Point2::new(
+                        (sum.position[0] / count) as f32,
+                        (sum.position[1] / count) as f32,
+                    ),
+                    pixel_count: sum.pixel_count,
+                    bounding_box: sum.bounding_box,
+                    perimeter: sum.perimeter,
+                }
+            })
+            .collect();
+
+        Segmentation {
+            width,
+            height,
+            labels,
+            regions,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+    pub fn superpixel_count(&self) -> usize {
+        self.regions.len()
+    }
+
+    /// Labels of all pixels in row-major order
+    pub fn labels(&self) -> &[u32] {
+        &self.labels
+    }
+
+    /// Label of the superpixel containing the pixel at (x, y), [`NO_LABEL`] if the
+    /// pixel was left out of clustering. Panics if (x, y) is outside the image.
+    pub fn label(&self, x: u32, y: u32) -> u32 {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is outside of the {}x{} segmentation",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+
+        self.labels[y as usize * self.width as usize + x as usize]
+    }
+
+    /// Statistics of the superpixel with the given label
+    pub fn region(&self, label: u32) -> Option<&Region> {
+        self.regions.get(label as usize)
+    }
+
+    /// Statistics of the superpixel containing the pixel at (x, y).
+    /// Panics if (x, y) is outside the image.
+    pub fn region_at(&self, x: u32, y: u32) -> Option<&Region> {
+        self.region(self.label(x, y))
+    }
+
+    /// Iterates over all superpixels, ordered by label
+    pub fn regions(&self) -> impl Iterator<Item = &Region> {
+        self.regions.iter()
+    }
+
+    /// Paints every superpixel with its mean color, pixels left out of clustering are black
+    pub fn to_rgb_image(&self) -> RgbImage {
+        let colors: Vec<_> = self
+            .regions
+            .iter()
+            .map(|r| r.mean_rgb().as_image_rgb())
+            .collect();
+
+        RgbImage::from_fn(self.width, self.height, |x, y| {
+            colors
+                .get(self.label(x, y) as usize)
+                .copied()
+                .unwrap_or(Rgb([0, 0, 0]))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::colors::ColorCieLab;
+    use crate::segmentation::{Segmentation, NO_LABEL};
+    use cgmath::Point2;
+
+    #[test]
+    fn regions_should_hold_statistics() {
+        // Arrange
+        #[rustfmt::skip]
+        let labels: Vec<usize> = vec![
+            0, 0, 2, 2,
+            0, 0, 2, 2,
+            0, 0, 0, 2,
+        ];
+        let colors: Vec<ColorCieLab> = labels
+            .iter()
+            .map(|l| ColorCieLab::new(*l as f32 * 10.0, 0.0, 0.0))
+            .collect();
+
+        // Act
+        let segmentation = Segmentation::new(4, 3, labels.into_iter().zip(colors));
+
+        // Assert
+        assert_eq!(2, segmentation.superpixel_count());
+        assert_eq!(1, segmentation.label(3, 2));
+
+        let first = segmentation.region(0).unwrap();
+        assert_eq!(7, first.pixel_count());
+        assert_eq!(0.0, first.mean_lab().l());
+        assert_eq!(Point2::new(0, 0), first.bounding_box().min());
+        assert_eq!(Point2::new(2, 2), first.bounding_box().max());
+        assert_eq!(12, first.perimeter());
+
+        let second = segmentation.region_at(3, 0).unwrap();
+        assert_eq!(1, second.label());
+        assert_eq!(5, second.pixel_count());
+        assert_eq!(20.0, second.mean_lab().l());
+        assert!((second.centroid().x - 2.6).abs() < 1e-5);
+        assert!((second.centroid().y - 0.8).abs() < 1e-5);
+        assert_eq!(10, second.perimeter());
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of the 2x2 segmentation")]
+    fn label_outside_the_image_should_panic() {
+        // Arrange
+        let labels = vec![0, 0, 1, 1];
+        let colors = vec![ColorCieLab::new(0.0, 0.0, 0.0); 4];
+        let segmentation = Segmentation::new(2, 2, labels.into_iter().zip(colors));
+
+        // Act, (2, 0) would wrap to (0, 1)
+        segmentation.label(2, 0);
+    }
+
+    #[test]
+    fn excluded_pixels_should_have_no_label() {
+        // Arrange
+        let labels = vec![usize::MAX, 4, 4, usize::MAX];
+        let colors = vec![ColorCieLab::new(0.0, 0.0, 0.0); 4];
+
+        // Act
+        let segmentation = Segmentation::new(2, 2, labels.into_iter().zip(colors));
+
+        // Assert
+        assert_eq!(&[NO_LABEL, 0, 0, NO_LABEL], segmentation.labels());
+        assert_eq!(1, segmentation.superpixel_count());
+        assert_eq!(2, segmentation.region(0).unwrap().pixel_count());
+        assert!(segmentation.region_at(0, 0).is_none());
+    }
+}